@@ -0,0 +1,271 @@
+import { describe, it } from 'node:test';
+import { strict as assert, AssertionError } from 'node:assert/strict';
+import { _power_, _power_table_ } from '../runtime.mts';
+
+function messageOf (fn: () => unknown): string {
+  try {
+    fn();
+  } catch (e) {
+    assert(e instanceof AssertionError);
+    return e.message;
+  }
+  assert.fail('should have thrown');
+}
+
+describe('_power_table_', () => {
+  const truthy = '1';
+  const falsy = 0;
+
+  it('renders the diagram from positions in the assertion table', () => {
+    const _patbl = _power_table_([
+      ['assert(truthy === falsy)', [14, 7, 23, 7, 7, 13, 18, 18, 23], { binexp: '===' }]
+    ]);
+    const _pasrt1 = _patbl(0, assert, null);
+    const _parg1 = _pasrt1.recorder(0);
+    const message = messageOf(() => _pasrt1.run(_parg1.rec(_parg1.tap(truthy, 1, { hint: 'left' }) === _parg1.tap(falsy, 2, { hint: 'right' }), 0)));
+    assert.equal(message, `
+
+# Human-readable format:
+assert(truthy === falsy)
+       |      |   |
+       |      |   0
+       |      false
+       "1"
+
+# AI-readable format:
+Assertion failed: assert(truthy === falsy)
+=== arg:0 ===
+Step 1: \`truthy\` => "1"
+Step 2: \`falsy\` => 0
+Step 3: \`truthy === falsy\` => false
+
+"1" === 0
+`);
+  });
+
+  it('does not throw when the assertion passes', () => {
+    const _patbl = _power_table_([
+      ['assert.equal(truthy, "1")', [13, 13, 19]]
+    ]);
+    const _pasrt1 = _patbl(0, assert.equal, assert);
+    const _parg1 = _pasrt1.recorder(0);
+    assert.doesNotThrow(() => _pasrt1.run(_parg1.rec(truthy, 0), '1'));
+  });
+
+  it('keeps the caller at the top of the stack trace', () => {
+    const _patbl = _power_table_([
+      ['assert(truthy === falsy)', [14, 7, 23, 7, 7, 13, 18, 18, 23], { binexp: '===' }],
+      ['expect(falsy).toBe(truthy)', [7, 7, 12, 19, 19, 25]]
+    ]);
+    const expect = (actual: unknown) => ({
+      toBe (expected: unknown) {
+        assert.equal(actual, expected);
+      }
+    });
+    function runFailingAssertion () {
+      const _pasrt1 = _patbl(0, assert, null);
+      const _parg1 = _pasrt1.recorder(0);
+      return _pasrt1.run(_parg1.rec(_parg1.tap(truthy, 1, { hint: 'left' }) === _parg1.tap(falsy, 2, { hint: 'right' }), 0));
+    }
+    function runFailingChain () {
+      const _pasrt2 = _patbl(1, expect, null);
+      const _parg2 = _pasrt2.recorder(0);
+      const _parg3 = _pasrt2.recorder(1);
+      return _pasrt2.runChain((_paexpect: Function, _paval0: unknown, _paval1: unknown) => _paexpect(_paval0).toBe(_paval1), _parg2.rec(falsy, 0), _parg3.rec(truthy, 1));
+    }
+    for (const [fn, name] of [[runFailingAssertion, 'runFailingAssertion'], [runFailingChain, 'runFailingChain']] as const) {
+      assert.throws(fn, (e: unknown) => {
+        assert(e instanceof AssertionError);
+        const topFrame = (e.stack ?? '').split('\n').find((line) => line.trimStart().startsWith('at '));
+        assert.match(topFrame ?? '', new RegExp(`at ${name} `));
+        return true;
+      });
+    }
+  });
+
+  it('rejects indexes out of the assertion table', () => {
+    const _patbl = _power_table_([
+      ['assert(truthy)', [7, 7, 13]]
+    ]);
+    assert.throws(() => _patbl(1, assert, null), /assertion 1 is not in the assertion table/);
+    const _parg1 = _patbl(0, assert, null).recorder(0);
+    assert.throws(() => _parg1.tap(truthy, 1), /capture index 1 is out of the assertion table/);
+  });
+});
+
+describe('tryRun', () => {
+  const truthy = '1';
+  const falsy = 0;
+
+  it('does not run the instrumented assertion when the original one passes', () => {
+    const _pasrt1 = _power_(assert, null, 'assert(truthy)');
+    const _parg1 = _pasrt1.recorder(0);
+    let poweredRuns = 0;
+    _pasrt1.tryRun(() => assert(truthy), () => {
+      poweredRuns++;
+      return _pasrt1.run(_parg1.rec(truthy, 7, 7, 13));
+    });
+    assert.equal(poweredRuns, 0);
+  });
+
+  it('reports the failure of the instrumented assertion', () => {
+    const _pasrt1 = _power_(assert, null, 'assert(truthy === falsy)', { binexp: '===' });
+    const _parg1 = _pasrt1.recorder(0);
+    const message = messageOf(() => _pasrt1.tryRun(() => assert(truthy === falsy as unknown), () => _pasrt1.run(_parg1.rec(_parg1.tap(truthy, 7, 7, 13, { hint: 'left' }) === _parg1.tap(falsy, 18, 18, 23, { hint: 'right' }), 14, 7, 23))));
+    assert.match(message, /^assert\(truthy === falsy\)\n {7}\| {6}\| {3}\|$/m);
+    assert.match(message, /^"1" === 0$/m);
+  });
+
+  it('reports the original failure when the instrumented assertion passes on re-run', () => {
+    const _pasrt1 = _power_(assert, null, 'assert(flaky())');
+    const _parg1 = _pasrt1.recorder(0);
+    let calls = 0;
+    const flaky = () => ++calls > 1;
+    const message = messageOf(() => _pasrt1.tryRun(() => assert(flaky()), () => _pasrt1.run(_parg1.rec(flaky(), 7, 7, 14))));
+    assert.equal(calls, 2);
+    assert.doesNotMatch(message, /# Human-readable format:/);
+  });
+
+  it('rethrows errors other than assertion errors without re-running', () => {
+    const _pasrt1 = _power_(assert, null, 'assert(boom())');
+    let poweredRuns = 0;
+    assert.throws(() => _pasrt1.tryRun(() => { throw new TypeError('boom'); }, () => { poweredRuns++; }), TypeError);
+    assert.equal(poweredRuns, 0);
+  });
+
+  it('runs the instrumented assertion when the original promise rejects', async () => {
+    const _pasrt1 = _power_(assert.rejects, assert, 'assert.rejects(promise)');
+    const _parg1 = _pasrt1.recorder(0);
+    const promise = Promise.resolve(truthy);
+    let poweredRuns = 0;
+    await assert.rejects(async () => _pasrt1.tryRun(() => assert.rejects(promise), () => {
+      poweredRuns++;
+      return _pasrt1.run(_parg1.rec(promise, 15, 15, 22));
+    }), AssertionError);
+    assert.equal(poweredRuns, 1);
+  });
+});
+
+describe('spreadRecorder', () => {
+  it('spreads the recorded argument into the arguments of the assertion', () => {
+    const x = 1;
+    const rest = [2, 'message'];
+    const _pasrt1 = _power_(assert.equal, assert, 'assert.equal(x, ...rest)');
+    const _parg1 = _pasrt1.recorder(0);
+    const _parg2 = _pasrt1.spreadRecorder(1);
+    assert.throws(() => _pasrt1.run(_parg1.rec(x, 13, 13, 14), _parg2.rec(rest, 19, 19, 23)), (e: unknown) => {
+      assert(e instanceof AssertionError);
+      assert.equal(e.actual, 1);
+      assert.equal(e.expected, 2);
+      assert.match(e.message, /^assert\.equal\(x, \.\.\.rest\)\n {13}\| {5}\|\n {13}\| {5}\[2,"message"\]\n {13}1$/m);
+      return true;
+    });
+  });
+
+  it('spreads iterables other than arrays', () => {
+    const args = new Set([0]);
+    const _pasrt1 = _power_(assert, null, 'assert(...args)');
+    const _parg1 = _pasrt1.spreadRecorder(0);
+    // assert(0), not assert(args)
+    assert.throws(() => _pasrt1.run(_parg1.rec(args, 10, 10, 14)), AssertionError);
+  });
+});
+
+describe('runChain', () => {
+  // stands in for expect of jest and vitest, whose errors have matcherResult
+  class MatcherError extends Error {
+    matcherResult = { pass: false };
+  }
+  const expect = (actual: unknown) => ({
+    toBe (expected: unknown) {
+      if (!Object.is(actual, expected)) {
+        throw new MatcherError(`expected ${String(actual)} to be ${String(expected)}`);
+      }
+    },
+    resolves: {
+      async toBe (expected: unknown) {
+        const resolved = await actual;
+        if (!Object.is(resolved, expected)) {
+          throw new MatcherError(`expected ${String(resolved)} to be ${String(expected)}`);
+        }
+      }
+    }
+  });
+  const user = { roles: ['guest'] };
+
+  function runFailingChain () {
+    const _pasrt1 = _power_(expect, null, "expect(user.roles.includes('admin')).toBe(true)");
+    const _parg1 = _pasrt1.recorder(0);
+    const _parg2 = _pasrt1.recorder(1);
+    return _pasrt1.runChain((_paexpect: Function, _paval0: unknown, _paval1: unknown) => _paexpect(_paval0).toBe(_paval1), _parg1.rec(_parg1.tap(_parg1.tap(user, 7, 7, 11).roles, 12, 7, 17).includes(_parg1.tap('admin', 27, 27, 34)), 18, 7, 35), _parg2.rec(true, 42, 42, 46));
+  }
+
+  it('renders the diagram of a failing matcher chain', () => {
+    assert.match(messageOf(runFailingChain), /^expect\(user\.roles\.includes\('admin'\)\)\.toBe\(true\)\n {7}\| {4}\| {5}\| {8}\| {14}\|$/m);
+  });
+
+  it('keeps the caller in the stack trace', () => {
+    assert.throws(runFailingChain, (e: unknown) => {
+      assert(e instanceof AssertionError);
+      assert.match(e.stack ?? '', /at runFailingChain /);
+      return true;
+    });
+  });
+
+  it('renders the diagram of a rejected matcher chain', async () => {
+    const _pasrt1 = _power_(expect, null, 'expect(promise).resolves.toBe(true)');
+    const _parg1 = _pasrt1.recorder(0);
+    const _parg2 = _pasrt1.recorder(1);
+    const promise = Promise.resolve(false);
+    await assert.rejects(
+      async () => _pasrt1.runChain((_paexpect: Function, _paval0: unknown, _paval1: unknown) => _paexpect(_paval0).resolves.toBe(_paval1), _parg1.rec(promise, 7, 7, 14), _parg2.rec(true, 30, 30, 34)),
+      (e: unknown) => e instanceof AssertionError && e.message.includes('# Human-readable format:')
+    );
+  });
+});
+
+describe('errors of invariant', () => {
+  const a = { b: 0 };
+
+  // tiny-invariant throws plain Error, and invariant names its errors 'Invariant Violation'
+  for (const [name, invariant] of [
+    ['tiny-invariant', (condition: unknown) => { if (!condition) throw new Error('Invariant failed'); }],
+    ['invariant', (condition: unknown) => { if (!condition) throw Object.assign(new Error('Minified exception occurred'), { name: 'Invariant Violation' }); }]
+  ] as const) {
+    it(`renders the diagram for errors of ${name}`, () => {
+      const _pasrt1 = _power_(invariant, null, 'invariant(a.b)');
+      const _parg1 = _pasrt1.recorder(0);
+      assert.match(messageOf(() => _pasrt1.run(_parg1.rec(_parg1.tap(a, 10, 10, 11).b, 12, 10, 13))), /^invariant\(a\.b\)\n {10}\| \|\n {10}\| 0\n {10}Object\{b:0\}$/m);
+    });
+  }
+
+  it('rethrows other errors as is', () => {
+    const _pasrt1 = _power_(() => { throw new Error('Invariant'); }, null, 'invariant(a.b)');
+    const _parg1 = _pasrt1.recorder(0);
+    assert.throws(() => _pasrt1.run(_parg1.rec(a.b, 12, 10, 13)), (e: unknown) => e instanceof Error && !(e instanceof AssertionError));
+  });
+});
+
+describe('console.assert', () => {
+  const items: unknown[] = [];
+
+  it('logs the diagram instead of throwing', (t) => {
+    const log = t.mock.method(console, 'assert', () => {});
+    const _pasrt1 = _power_(console.assert, console, "console.assert(items.length > 0, 'no items')");
+    const _parg1 = _pasrt1.recorder(0);
+    assert.doesNotThrow(() => _pasrt1.run(_parg1.rec(_parg1.tap(_parg1.tap(items, 15, 15, 20).length, 21, 15, 27) > _parg1.tap(0, 30, 30, 31), 28, 15, 31), 'no items'));
+    assert.equal(log.mock.callCount(), 1);
+    const [condition, diagram, ...data] = log.mock.calls[0].arguments;
+    assert.equal(condition, false);
+    assert.match(String(diagram), /^console\.assert\(items\.length > 0, 'no items'\)\n {15}\|     \|      \| \|\n {15}\|     \|      \| 0$/m);
+    assert.deepEqual(data, ['no items']);
+  });
+
+  it('logs nothing when the condition holds', (t) => {
+    const log = t.mock.method(console, 'assert', () => {});
+    const _pasrt1 = _power_(console.assert, console, 'console.assert(items)');
+    const _parg1 = _pasrt1.recorder(0);
+    _pasrt1.run(_parg1.rec(items, 15, 15, 20));
+    assert.equal(log.mock.callCount(), 0);
+  });
+});
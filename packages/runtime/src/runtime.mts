@@ -12,6 +12,10 @@ type PowerAssertMetadata = {
 
 type CapturedValueMetadata = {
   hint?: string;
+  // expression tree, emitted with the expressionTree option of the transpiler
+  id?: number;
+  kind?: string;
+  parent?: number;
 };
 
 type CapturedValue = {
@@ -31,16 +35,24 @@ type RecordedArgument = {
 
 type ArgumentRecorder = {
   tap(value: unknown, markerPos: number, startPos: number, endPos: number, metadata?: CapturedValueMetadata): unknown;
-  rec(value: unknown, markerPos?: number, startPos?: number, endPos?: number): ArgumentRecorder;
+  rec(value: unknown, markerPos?: number, startPos?: number, endPos?: number, metadata?: CapturedValueMetadata): ArgumentRecorder;
 };
 
 type PowerAssert = {
   recorder(argumentNumber: number): ArgumentRecorder;
+  spreadRecorder(argumentNumber: number): ArgumentRecorder;
   run(...args: unknown[]): unknown;
+  runChain(chain: (callee: Function, ...args: unknown[]) => unknown, ...args: unknown[]): unknown;
+  tryRun(original: () => unknown, powered: () => unknown): unknown;
 };
 
 type PowerAssertRuntime = (callee: Function, receiver: unknown, content: string, extra?: unknown) => PowerAssert;
 
+// [content, flattened [markerPos, startPos, endPos] of each capture, extra]
+type AssertionTableEntry = [string, number[], unknown?];
+
+type PowerAssertTable = (index: number, callee: Function, receiver: unknown) => PowerAssert;
+
 function isPromiseLike (o: any): o is Promise<any> {
   return typeof o === 'object' && o !== null && typeof o.then === 'function' && typeof o.catch === 'function';
 }
@@ -67,14 +79,16 @@ const wrap = (v: unknown) => isPromiseLike(v) ? new $Promise$(v) : v;
 class ArgumentRecorderImpl implements ArgumentRecorder {
   readonly #powerAssert: PowerAssert;
   readonly #argumentNumber: number;
+  readonly #spread: boolean;
   #capturedValues: CapturedValue[];
   #recorded: RecordedArgument | null;
   #val: unknown;
   #evalOrder: number;
 
-  constructor (powerAssert: PowerAssert, argumentNumber: number) {
+  constructor (powerAssert: PowerAssert, argumentNumber: number, spread: boolean = false) {
     this.#powerAssert = powerAssert;
     this.#argumentNumber = argumentNumber;
+    this.#spread = spread;
     this.#capturedValues = [];
     this.#recorded = null;
     this.#val = null;
@@ -85,6 +99,11 @@ class ArgumentRecorderImpl implements ArgumentRecorder {
     return this.#val;
   }
 
+  // recorded value is the iterable of a spread argument, as in `assert(...args)`
+  isSpread (): boolean {
+    return this.#spread;
+  }
+
   ejectRecordedArgument (): RecordedArgument {
     const ret = this.#recorded;
     assert(ret !== null, 'ejectRecordedArgument() should be called after recording');
@@ -107,7 +126,7 @@ class ArgumentRecorderImpl implements ArgumentRecorder {
     return value;
   }
 
-  rec (value: unknown, markerPos?: number, startPos?: number, endPos?: number): ArgumentRecorder {
+  rec (value: unknown, markerPos?: number, startPos?: number, endPos?: number, metadata?: CapturedValueMetadata): ArgumentRecorder {
     try {
       if (typeof markerPos === 'undefined') {
         // node right under the assertion is not captured
@@ -123,7 +142,8 @@ class ArgumentRecorderImpl implements ArgumentRecorder {
         markerPos,
         startPos,
         endPos,
-        evalOrder
+        evalOrder,
+        metadata
       };
       this.#capturedValues.push(cap);
       // capture asesert.throws, assert.doesNotThrow, assert.rejects, assert.doesNotReject
@@ -153,6 +173,16 @@ class ArgumentRecorderImpl implements ArgumentRecorder {
   }
 }
 
+// expands spread arguments into actual arguments
+function actualArgs (poweredArgs: unknown[]): unknown[] {
+  return poweredArgs.flatMap((a) => {
+    if (a instanceof ArgumentRecorderImpl && a.isSpread()) {
+      return [...(a.actualValue() as Iterable<unknown>)];
+    }
+    return [actual(a)];
+  });
+}
+
 function actual (v: unknown): unknown {
   if (v instanceof ArgumentRecorderImpl) {
     return v.actualValue();
@@ -187,7 +217,15 @@ function eject (v: unknown): PoweredArgument | NonPoweredArgument {
 }
 
 function isAssertionError (e: unknown): e is AssertionError {
-  return e instanceof Error && /^AssertionError/.test(e.name);
+  if (!(e instanceof Error)) {
+    return false;
+  }
+  // matcherResult is set by expect() of jest, whose errors are not named AssertionError
+  if (/^AssertionError/.test(e.name) || 'matcherResult' in e) {
+    return true;
+  }
+  // invariant names its errors 'Invariant Violation', while tiny-invariant throws plain Error with 'Invariant failed'
+  return e.name === 'Invariant Violation' || e.message.startsWith('Invariant failed');
 }
 
 function isMultiline (s: string): boolean {
@@ -211,78 +249,147 @@ class PowerAssertImpl implements PowerAssert {
     return new ArgumentRecorderImpl(this, argumentNumber);
   }
 
-  run (...poweredArgs: unknown[]): unknown {
-    const actualArgs = poweredArgs.map((a) => actual(a));
-    try {
-      return this.#callee.apply(this.#receiver, actualArgs);
-    } catch (e: unknown) {
+  spreadRecorder (argumentNumber: number): ArgumentRecorder {
+    return new ArgumentRecorderImpl(this, argumentNumber, true);
+  }
+
+  // runs the uninstrumented assertion first, and the instrumented one only to report its failure
+  tryRun (original: () => unknown, powered: () => unknown): unknown {
+    const onFailure = (e: unknown): unknown => {
       if (!isAssertionError(e)) {
         throw e;
       }
-      const recorded = poweredArgs.map((p) => eject(p));
-      const logs = [];
-      for (const rec of recorded) {
-        if (rec.type === 'PoweredArgument') {
-          for (const cap of rec.capturedValues) {
-            logs.push(cap);
-          }
-        }
+      const ret = powered();
+      if (isPromiseLike(ret)) {
+        return ret.then(() => { throw e; });
       }
-      // console.log(logs);
+      // passed on re-run, so the assertion is not deterministic. report the original failure
+      throw e;
+    };
+    let ret: unknown;
+    try {
+      ret = original();
+    } catch (e: unknown) {
+      return onFailure(e);
+    }
+    return isPromiseLike(ret) ? ret.then(undefined, onFailure) : ret;
+  }
 
-      const originalMessage = e.message;
-      const isOriginalMessageGenerated = e.generatedMessage;
-      const newMessageFragments: string[] = [];
-      const assertionLine = this.#assertionMetadata.content;
+  run (...poweredArgs: unknown[]): unknown {
+    return this.runFrom(this.run, poweredArgs);
+  }
 
-      newMessageFragments.push('');
-      newMessageFragments.push('');
-      if (isMultiline(assertionLine)) {
-        newMessageFragments.push(assertionLine);
-      } else {
-        // rethrow AssertionError with diagram message
-        const diagram = renderDiagram(assertionLine, logs);
-        newMessageFragments.push('# Human-readable format:');
-        newMessageFragments.push(diagram);
-      }
+  // stackStartFn is the method called by instrumented code, which is the one of IndexedPowerAssert on the compact protocol
+  runFrom (stackStartFn: Function, poweredArgs: unknown[]): unknown {
+    if (this.#callee === globalThis.console?.assert) {
+      return this.#logWithDiagram(poweredArgs);
+    }
+    try {
+      return this.#callee.apply(this.#receiver, actualArgs(poweredArgs));
+    } catch (e: unknown) {
+      this.#rethrowWithDiagram(e, poweredArgs, stackStartFn);
+    }
+  }
 
-      newMessageFragments.push('');
-      const stepwiseLines = renderStepwise(assertionLine, logs);
-      newMessageFragments.push('# AI-readable format:');
-      newMessageFragments.push('Assertion failed: ' + assertionLine);
-      newMessageFragments.push(stepwiseLines);
+  // runs matcher chains such as expect(actual).not.toBe(expected), given as (expect, actual, expected) => expect(actual).not.toBe(expected)
+  runChain (chain: (callee: Function, ...args: unknown[]) => unknown, ...poweredArgs: unknown[]): unknown {
+    return this.runChainFrom(this.runChain, chain, poweredArgs);
+  }
+
+  runChainFrom (stackStartFn: Function, chain: (callee: Function, ...args: unknown[]) => unknown, poweredArgs: unknown[]): unknown {
+    let ret: unknown;
+    try {
+      ret = chain(this.#callee.bind(this.#receiver), ...actualArgs(poweredArgs));
+    } catch (e: unknown) {
+      this.#rethrowWithDiagram(e, poweredArgs, stackStartFn);
+    }
+    // expect(promise).resolves.toBe(expected). runChain has returned by the rejection, so the trace is left uncut
+    return isPromiseLike(ret) ? ret.then(undefined, (e: unknown) => this.#rethrowWithDiagram(e, poweredArgs)) : ret;
+  }
+
+  // console.assert logs its failure instead of throwing, so the diagram is logged in place of its message
+  #logWithDiagram (poweredArgs: unknown[]): void {
+    const [condition, ...data] = actualArgs(poweredArgs);
+    if (condition) {
+      return;
+    }
+    const logs = capturedValuesOf(poweredArgs);
+    this.#callee.call(this.#receiver, false, this.#renderDiagram(logs).join('\n'), ...data);
+  }
+
+  // the generated stack trace omits stackStartFn and the frames it called, so it has to be the entry point on the stack
+  #rethrowWithDiagram (e: unknown, poweredArgs: unknown[], stackStartFn?: Function): never {
+    if (!isAssertionError(e)) {
+      throw e;
+    }
+    const logs = capturedValuesOf(poweredArgs);
+    // console.log(logs);
+
+    const originalMessage = e.message;
+    const isOriginalMessageGenerated = e.generatedMessage;
+    const newMessageFragments = this.#renderDiagram(logs);
+
+    const newAssertionErrorProps = {
+      message: originalMessage,
+      operator: e.operator,
+      actual: e.actual,
+      expected: e.expected,
+      generatedMessage: true,  // always true because this is generated by power-assert
+      stackStartFn
+    };
 
+    // BinaryExpression analysis
+    if (this.#assertionMetadata.binexp) {
+      const binexp = this.#assertionMetadata.binexp;
+      newAssertionErrorProps.operator = binexp;
+      newAssertionErrorProps.actual = logs.find((log) => log.metadata?.hint === 'left')?.value;
+      newAssertionErrorProps.expected = logs.find((log) => log.metadata?.hint === 'right')?.value;
+      const { expected, actual, operator } = newAssertionErrorProps;
+      newMessageFragments.push(`${stringify(actual)} ${operator} ${stringify(expected)}`);
       newMessageFragments.push('');
+    } else if (!isOriginalMessageGenerated) {
+      // if written by user, keep original assertion message for non-BinaryExpression assertions
+      newMessageFragments.push(originalMessage);
+    } else {
+      // discard generated message since power-assert generates more detailed message
+    }
+    newAssertionErrorProps.message = newMessageFragments.join('\n');
 
-      const newAssertionErrorProps = {
-        message: originalMessage,
-        operator: e.operator,
-        actual: e.actual,
-        expected: e.expected,
-        generatedMessage: true,  // always true because this is generated by power-assert
-        stackStartFn: this.run // the generated stack trace omits frames before this function.
-      };
+    throw new AssertionError(newAssertionErrorProps);
+  }
 
-      // BinaryExpression analysis
-      if (this.#assertionMetadata.binexp) {
-        const binexp = this.#assertionMetadata.binexp;
-        newAssertionErrorProps.operator = binexp;
-        newAssertionErrorProps.actual = logs.find((log) => log.metadata?.hint === 'left')?.value;
-        newAssertionErrorProps.expected = logs.find((log) => log.metadata?.hint === 'right')?.value;
-        const { expected, actual, operator } = newAssertionErrorProps;
-        newMessageFragments.push(`${stringify(actual)} ${operator} ${stringify(expected)}`);
-        newMessageFragments.push('');
-      } else if (!isOriginalMessageGenerated) {
-        // if written by user, keep original assertion message for non-BinaryExpression assertions
-        newMessageFragments.push(originalMessage);
-      } else {
-        // discard generated message since power-assert generates more detailed message
-      }
-      newAssertionErrorProps.message = newMessageFragments.join('\n');
+  #renderDiagram (logs: CapturedValue[]): string[] {
+    const newMessageFragments: string[] = [];
+    const assertionLine = this.#assertionMetadata.content;
+    newMessageFragments.push('');
+    newMessageFragments.push('');
+    if (isMultiline(assertionLine)) {
+      newMessageFragments.push(assertionLine);
+    } else {
+      const diagram = renderDiagram(assertionLine, logs);
+      newMessageFragments.push('# Human-readable format:');
+      newMessageFragments.push(diagram);
+    }
+
+    newMessageFragments.push('');
+    const stepwiseLines = renderStepwise(assertionLine, logs);
+    newMessageFragments.push('# AI-readable format:');
+    newMessageFragments.push('Assertion failed: ' + assertionLine);
+    newMessageFragments.push(stepwiseLines);
 
-      throw new AssertionError(newAssertionErrorProps);
+    newMessageFragments.push('');
+    return newMessageFragments;
+  }
+}
+
+function capturedValuesOf (poweredArgs: unknown[]): CapturedValue[] {
+  const logs: CapturedValue[] = [];
+  for (const rec of poweredArgs.map((p) => eject(p))) {
+    if (rec.type === 'PoweredArgument') {
+      logs.push(...rec.capturedValues);
     }
   }
+  return logs;
 }
 
 function createPowerAssertMetadata (content: string, extra?: unknown): PowerAssertMetadata {
@@ -297,6 +404,82 @@ const _power_: PowerAssertRuntime = (callee: Function, receiver: unknown, conten
   return new PowerAssertImpl(callee, receiver, createPowerAssertMetadata(content, extra));
 };
 
+// capture index is the id of the expression tree in compact protocol
+function withId (metadata: CapturedValueMetadata | undefined, captureIndex: number): CapturedValueMetadata | undefined {
+  return metadata?.kind === undefined ? metadata : { ...metadata, id: captureIndex };
+}
+
+// recorder of the compact protocol, referencing positions by capture index
+class IndexedArgumentRecorder implements ArgumentRecorder {
+  readonly #recorder: ArgumentRecorderImpl;
+  readonly #positions: number[];
+
+  constructor (recorder: ArgumentRecorderImpl, positions: number[]) {
+    this.#recorder = recorder;
+    this.#positions = positions;
+  }
+
+  tap (value: unknown, captureIndex: number, metadata?: CapturedValueMetadata): unknown {
+    const [markerPos, startPos, endPos] = this.#positionsOf(captureIndex);
+    return this.#recorder.tap(value, markerPos, startPos, endPos, withId(metadata, captureIndex));
+  }
+
+  rec (value: unknown, captureIndex?: number, metadata?: CapturedValueMetadata): ArgumentRecorder {
+    if (typeof captureIndex === 'undefined') {
+      return this.#recorder.rec(value);
+    }
+    const [markerPos, startPos, endPos] = this.#positionsOf(captureIndex);
+    // returns the wrapped recorder so that run() can eject recorded arguments
+    return this.#recorder.rec(value, markerPos, startPos, endPos, withId(metadata, captureIndex));
+  }
+
+  #positionsOf (captureIndex: number): [number, number, number] {
+    const offset = captureIndex * 3;
+    assert(offset + 3 <= this.#positions.length, `capture index ${captureIndex} is out of the assertion table`);
+    return [this.#positions[offset], this.#positions[offset + 1], this.#positions[offset + 2]];
+  }
+}
+
+class IndexedPowerAssert implements PowerAssert {
+  readonly #powerAssert: PowerAssertImpl;
+  readonly #positions: number[];
+
+  constructor (powerAssert: PowerAssertImpl, positions: number[]) {
+    this.#powerAssert = powerAssert;
+    this.#positions = positions;
+  }
+
+  recorder (argumentNumber: number): ArgumentRecorder {
+    return new IndexedArgumentRecorder(new ArgumentRecorderImpl(this.#powerAssert, argumentNumber), this.#positions);
+  }
+
+  spreadRecorder (argumentNumber: number): ArgumentRecorder {
+    return new IndexedArgumentRecorder(new ArgumentRecorderImpl(this.#powerAssert, argumentNumber, true), this.#positions);
+  }
+
+  run (...poweredArgs: unknown[]): unknown {
+    return this.#powerAssert.runFrom(this.run, poweredArgs);
+  }
+
+  runChain (chain: (callee: Function, ...args: unknown[]) => unknown, ...poweredArgs: unknown[]): unknown {
+    return this.#powerAssert.runChainFrom(this.runChain, chain, poweredArgs);
+  }
+
+  tryRun (original: () => unknown, powered: () => unknown): unknown {
+    return this.#powerAssert.tryRun(original, powered);
+  }
+}
+
+const _power_table_ = (entries: AssertionTableEntry[]): PowerAssertTable => {
+  return (index: number, callee: Function, receiver: unknown) => {
+    const entry = entries[index];
+    assert(entry !== undefined, `assertion ${index} is not in the assertion table`);
+    const [content, positions, extra] = entry;
+    return new IndexedPowerAssert(new PowerAssertImpl(callee, receiver, createPowerAssertMetadata(content, extra)), positions);
+  };
+};
+
 export {
-  _power_
+  _power_,
+  _power_table_
 };
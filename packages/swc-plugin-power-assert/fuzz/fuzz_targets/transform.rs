@@ -0,0 +1,69 @@
+//! Crash-freedom of the transform on arbitrary input.
+//!
+//! `cargo +nightly fuzz run transform` from `packages/swc-plugin-power-assert`.
+#![no_main]
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use libfuzzer_sys::fuzz_target;
+use swc_core::common::{sync::Lrc, FileName, SourceMap};
+use swc_core::ecma::ast::{EsVersion, Pass, Program};
+use swc_core::ecma::codegen::{text_writer::JsWriter, Emitter};
+use swc_core::ecma::parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax};
+use swc_plugin_power_assert::{power_assert, Config};
+
+static INIT: Once = Once::new();
+
+// deeply nested input overflows the stack of the (recursive descent) parser first
+fn is_too_deep(code: &str) -> bool {
+    let mut depth: i32 = 0;
+    for c in code.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ => {}
+        }
+        if depth > 64 {
+            return true;
+        }
+    }
+    false
+}
+
+fuzz_target!(|data: &[u8]| {
+    // panics in swc_ecma_parser are out of scope, so the abort-on-panic hook of libfuzzer is
+    // replaced with explicit aborts on panics of the transform and codegen below
+    INIT.call_once(|| panic::set_hook(Box::new(|_| {})));
+    let Ok(code) = std::str::from_utf8(data) else {
+        return;
+    };
+    if is_too_deep(code) {
+        return;
+    }
+    for syntax in [Syntax::Es(EsSyntax { jsx: true, ..Default::default() }), Syntax::Typescript(TsSyntax::default())] {
+        let cm: Lrc<SourceMap> = Default::default();
+        // import an assertion module so that the input is always traversed
+        let fm = cm.new_source_file(FileName::Anon.into(), format!("import assert from 'node:assert';\n{}", code));
+        let parsed = panic::catch_unwind(|| parse_file_as_module(&fm, syntax, EsVersion::latest(), None, &mut vec![]));
+        let Ok(Ok(module)) = parsed else {
+            continue;
+        };
+        let transformed = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut program = Program::Module(module);
+            power_assert(Config::default(), cm.clone()).process(&mut program);
+
+            let mut buf = Vec::new();
+            let mut emitter = Emitter {
+                cfg: Default::default(),
+                cm: cm.clone(),
+                comments: None,
+                wr: JsWriter::new(cm.clone(), "\n", &mut buf, None)
+            };
+            emitter.emit_program(&program).unwrap();
+        }));
+        if transformed.is_err() {
+            eprintln!("transform panicked on:\n{}", fm.src);
+            std::process::abort();
+        }
+    }
+});
@@ -0,0 +1,338 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use swc_core::common::{
+    comments::SingleThreadedComments,
+    errors::{ColorConfig, Handler, HANDLER},
+    source_map::SourceMapGenConfig,
+    sync::Lrc,
+    FileName,
+    Globals,
+    Mark,
+    SourceMap,
+    GLOBALS
+};
+use swc_core::ecma::ast::EsVersion;
+use swc_core::ecma::codegen::{text_writer::JsWriter, Emitter};
+use swc_core::ecma::parser::{parse_file_as_module, parse_file_as_program, EsSyntax, Syntax, TsSyntax};
+use swc_core::ecma::transforms::base::resolver;
+use swc_plugin_power_assert::{power_assert_with_unresolved_mark, CalleePattern, Config, ModuleSpecifier, Preset, Protocol};
+
+const EXTENSIONS: [&str; 8] = ["js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx"];
+
+/// Instruments assertions in JavaScript / TypeScript files with power-assert.
+#[derive(Debug, Parser)]
+#[command(name = "swc-power-assert", version, about)]
+struct Args {
+    /// Files or directories to instrument
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Directory to write instrumented files and source maps to.
+    /// Instrumented code of a single file is printed to stdout without source maps if omitted
+    #[arg(short = 'd', long)]
+    out_dir: Option<PathBuf>,
+
+    /// Module specifier to import the power-assert runtime from
+    #[arg(long)]
+    runtime: Option<String>,
+
+    /// Module to be instrumented, replacing the defaults. Can be given more than once.
+    /// `SOURCE=IMPORTED,...` restricts instrumentation to the listed named imports.
+    /// Sources containing `*` or `?` are globs such as `**/test-utils/*.js`
+    #[arg(long = "modules", value_name = "SOURCE[=IMPORTED,...]")]
+    modules: Vec<String>,
+
+    /// Assertion library to be instrumented in addition to the modules. Can be given more than once
+    #[arg(long = "preset", value_parser = ["chai", "uvu/assert", "@std/assert", "bun:test", "power-assert", "invariant"])]
+    presets: Vec<String>,
+
+    /// Global function to be instrumented when not shadowed, such as `assert` or `console.assert`.
+    /// Can be given more than once
+    #[arg(long = "globals", value_name = "NAME")]
+    globals: Vec<String>,
+
+    /// Regular expression of names of imported or declared functions to be instrumented, such as `^assert[A-Z]`.
+    /// Can be given more than once
+    #[arg(long = "callee-pattern", value_name = "REGEX", value_parser = parse_callee_pattern)]
+    callee_patterns: Vec<CalleePattern>,
+
+    /// Protocol between instrumented code and the runtime
+    #[arg(long, value_parser = ["inline", "compact"])]
+    protocol: Option<String>,
+
+    /// Emit the node kind and the parent capture of each capture
+    #[arg(long)]
+    expression_tree: bool,
+
+    /// Evaluate side-effect-free assertions uninstrumented first, and instrumented only on failure
+    #[arg(long)]
+    fast_path: bool,
+
+    /// Warn about assignments, updates, `delete` and calls to mutating methods in assertion arguments
+    #[arg(long)]
+    warn_side_effects: bool,
+
+    /// Do not capture literals, except operands of `===` and `==` in assertions
+    #[arg(long)]
+    no_capture_literals: bool,
+
+    /// Do not capture `this`
+    #[arg(long)]
+    no_capture_this: bool,
+
+    /// Instrument `expect(actual).<matchers...>(expected)` chains of `expect` imported from `vitest` or `@jest/globals`
+    #[arg(long)]
+    expect: bool,
+
+    /// Indexes of arguments to be instrumented for the function imported from the module, overriding the built-in table.
+    /// `default` names the default export, as in `tiny-invariant:default=0`. Can be given more than once
+    #[arg(long = "captured-arguments", value_name = "MODULE:IMPORTED=INDEX,...", value_parser = parse_captured_arguments)]
+    captured_arguments: Vec<(String, String, Vec<usize>)>
+}
+
+struct InputFile {
+    path: PathBuf,
+    // path relative to the output directory
+    relative_path: PathBuf
+}
+
+struct Instrumented {
+    code: String,
+    source_map: Vec<u8>
+}
+
+struct SourceMapConfig;
+
+impl SourceMapGenConfig for SourceMapConfig {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        f.to_string()
+    }
+
+    fn inline_sources_content(&self, _f: &FileName) -> bool {
+        true
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(err) = GLOBALS.set(&Globals::new(), || run(args)) {
+        eprintln!("swc-power-assert: {:#}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<()> {
+    let config = config_from(&args);
+    let mut input_files = Vec::new();
+    for input in args.inputs.iter() {
+        collect_input_files(input, &mut input_files)?;
+    }
+    match &args.out_dir {
+        Some(out_dir) => ensure_not_overwritten(out_dir, &input_files)?,
+        // outputs printed back to back could not be told apart
+        None if input_files.len() > 1 => bail!("--out-dir is required to instrument {} files", input_files.len()),
+        None => {}
+    }
+    for input_file in input_files.iter() {
+        let instrumented = instrument(&config, &input_file.path)?;
+        match &args.out_dir {
+            Some(out_dir) => write_instrumented(out_dir, input_file, &instrumented)?,
+            None => io::stdout().write_all(instrumented.code.as_bytes())?
+        }
+    }
+    Ok(())
+}
+
+fn config_from(args: &Args) -> Config {
+    let mut builder = Config::builder();
+    if let Some(runtime) = &args.runtime {
+        builder = builder.runtime(runtime);
+    }
+    if !args.modules.is_empty() {
+        builder = builder.modules(args.modules.iter().map(|module| match module.split_once('=') {
+            Some((source, imported)) => ModuleSpecifier::with_imports(source, imported.split(',')),
+            None => ModuleSpecifier::from(module.as_str())
+        }));
+    }
+    for preset in args.presets.iter() {
+        builder = builder.preset(match preset.as_str() {
+            "chai" => Preset::Chai,
+            "uvu/assert" => Preset::Uvu,
+            "@std/assert" => Preset::StdAssert,
+            "bun:test" => Preset::BunTest,
+            "power-assert" => Preset::PowerAssert,
+            _ => Preset::Invariant
+        });
+    }
+    for global in args.globals.iter() {
+        builder = builder.global(global);
+    }
+    for callee_pattern in args.callee_patterns.iter() {
+        builder = builder.callee_pattern(callee_pattern.clone());
+    }
+    match args.protocol.as_deref() {
+        Some("compact") => builder = builder.protocol(Protocol::Compact),
+        Some(_) => builder = builder.protocol(Protocol::Inline),
+        None => {}
+    }
+    for (module, imported, indexes) in args.captured_arguments.iter() {
+        builder = builder.captured_arguments(module, imported, indexes.iter().copied());
+    }
+    builder
+        .expression_tree(args.expression_tree)
+        .fast_path(args.fast_path)
+        .warn_side_effects(args.warn_side_effects)
+        .capture_literals(!args.no_capture_literals)
+        .capture_this(!args.no_capture_this)
+        .expect(args.expect)
+        .build()
+}
+
+// `MODULE:IMPORTED=` captures no arguments, while `MODULE:IMPORTED` without `=` is rejected as a likely typo.
+// modules are split at the last `:`, since specifiers such as node:assert contain one
+fn parse_captured_arguments(value: &str) -> Result<(String, String, Vec<usize>), String> {
+    let Some((module, imported, indexes)) = value.split_once('=')
+        .and_then(|(function, indexes)| function.rsplit_once(':').map(|(module, imported)| (module, imported, indexes))) else {
+        return Err(format!("expected MODULE:IMPORTED=INDEX,... but got `{}`", value));
+    };
+    if module.is_empty() || imported.is_empty() {
+        return Err(format!("module or imported name is empty in `{}`", value));
+    }
+    let indexes = match indexes.trim() {
+        "" => Vec::new(),
+        indexes => indexes.split(',').map(|index| {
+            index.trim().parse().map_err(|_| format!("`{}` is not an argument index", index))
+        }).collect::<Result<_, _>>()?
+    };
+    Ok((module.to_string(), imported.to_string(), indexes))
+}
+
+fn parse_callee_pattern(pattern: &str) -> Result<CalleePattern, regex::Error> {
+    CalleePattern::new(pattern)
+}
+
+fn is_target_file(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let is_declaration_file = [".d.ts", ".d.mts", ".d.cts"].iter().any(|suffix| file_name.ends_with(suffix));
+    !is_declaration_file && path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+fn collect_input_files(input: &Path, input_files: &mut Vec<InputFile>) -> Result<()> {
+    if input.is_file() {
+        let file_name = input.file_name().ok_or_else(|| anyhow!("{} is not a file", input.display()))?;
+        input_files.push(InputFile {
+            path: input.to_path_buf(),
+            relative_path: PathBuf::from(file_name)
+        });
+        return Ok(());
+    }
+    if !input.is_dir() {
+        bail!("{} does not exist", input.display());
+    }
+    let mut dirs = vec![input.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs::read_dir(&dir)
+            .with_context(|| format!("failed to read {}", dir.display()))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+        for path in entries {
+            let is_hidden_or_dependency = path.file_name().and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with('.') || name == "node_modules");
+            if path.is_dir() && !is_hidden_or_dependency {
+                dirs.push(path);
+            } else if path.is_file() && is_target_file(&path) {
+                let relative_path = path.strip_prefix(input)?.to_path_buf();
+                input_files.push(InputFile { path, relative_path });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn syntax_for(path: &Path) -> Syntax {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("ts" | "mts" | "cts") => Syntax::Typescript(TsSyntax::default()),
+        Some("tsx") => Syntax::Typescript(TsSyntax { tsx: true, ..Default::default() }),
+        Some("jsx") => Syntax::Es(EsSyntax { jsx: true, ..Default::default() }),
+        _ => Syntax::Es(EsSyntax::default())
+    }
+}
+
+fn instrument(config: &Config, path: &Path) -> Result<Instrumented> {
+    let cm: Lrc<SourceMap> = Default::default();
+    // absolute path in `sources`, so that source maps resolve wherever the output is written
+    let fm = fs::canonicalize(path).and_then(|absolute_path| cm.load_file(&absolute_path))
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+    let comments = SingleThreadedComments::default();
+    let syntax = syntax_for(path);
+    HANDLER.set(&handler, || {
+        let mut recovered_errors = Vec::new();
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("mjs" | "mts") => parse_file_as_module(&fm, syntax, EsVersion::latest(), Some(&comments), &mut recovered_errors).map(Into::into),
+            _ => parse_file_as_program(&fm, syntax, EsVersion::latest(), Some(&comments), &mut recovered_errors)
+        };
+        let program = parsed.map_err(|err| {
+            err.into_diagnostic(&handler).emit();
+            anyhow!("failed to parse {}", path.display())
+        })?;
+        for err in recovered_errors {
+            err.into_diagnostic(&handler).emit();
+        }
+
+        let unresolved_mark = Mark::new();
+        let top_level_mark = Mark::new();
+        let program = program.apply((
+            resolver(unresolved_mark, top_level_mark, syntax.typescript()),
+            power_assert_with_unresolved_mark(config.clone(), cm.clone(), unresolved_mark)
+        ));
+
+        let mut code = Vec::new();
+        let mut mappings = Vec::new();
+        {
+            let mut emitter = Emitter {
+                cfg: swc_core::ecma::codegen::Config::default().with_target(EsVersion::latest()),
+                cm: cm.clone(),
+                comments: Some(&comments),
+                wr: JsWriter::new(cm.clone(), "\n", &mut code, Some(&mut mappings))
+            };
+            emitter.emit_program(&program)?;
+        }
+        let mut source_map = Vec::new();
+        cm.build_source_map(&mappings, None, SourceMapConfig).to_writer(&mut source_map)?;
+        Ok(Instrumented {
+            code: String::from_utf8(code)?,
+            source_map
+        })
+    })
+}
+
+// writing into the directory of the inputs would replace sources with their instrumented outputs
+fn ensure_not_overwritten(out_dir: &Path, input_files: &[InputFile]) -> Result<()> {
+    for input_file in input_files.iter() {
+        let out_path = out_dir.join(&input_file.relative_path);
+        if out_path.exists() && fs::canonicalize(&out_path)? == fs::canonicalize(&input_file.path)? {
+            bail!("{} would be overwritten by its instrumented output, choose another --out-dir", input_file.path.display());
+        }
+    }
+    Ok(())
+}
+
+fn write_instrumented(out_dir: &Path, input_file: &InputFile, instrumented: &Instrumented) -> Result<()> {
+    let out_path = out_dir.join(&input_file.relative_path);
+    if let Some(parent) = out_path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let file_name = out_path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    let map_path = out_path.with_file_name(format!("{}.map", file_name));
+    let code = format!("{}\n//# sourceMappingURL={}.map\n", instrumented.code.trim_end(), file_name);
+    fs::write(&out_path, code).with_context(|| format!("failed to write {}", out_path.display()))?;
+    fs::write(&map_path, &instrumented.source_map).with_context(|| format!("failed to write {}", map_path.display()))?;
+    Ok(())
+}
@@ -0,0 +1,335 @@
+use std::collections::BTreeMap;
+use regex::Regex;
+use serde::Deserialize;
+use crate::preset::Preset;
+
+/// Module to be instrumented, same as `ModuleSpecifier` of transpiler-core.
+///
+/// A bare module name targets every binding imported from the module,
+/// while `{ source, imported }` targets only the listed named imports.
+/// Sources containing `*` or `?` are globs matched against import sources as written,
+/// such as `**/test-utils/*.js` for `./test-utils/assert.js` and `../test-utils/assert.js`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ModuleSpecifier {
+    Source(String),
+    Imports {
+        source: String,
+        imported: Vec<String>
+    }
+}
+
+impl ModuleSpecifier {
+    pub fn with_imports<S: Into<String>>(source: impl Into<String>, imported: impl IntoIterator<Item = S>) -> Self {
+        ModuleSpecifier::Imports {
+            source: source.into(),
+            imported: imported.into_iter().map(Into::into).collect()
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        match self {
+            ModuleSpecifier::Source(source) => source,
+            ModuleSpecifier::Imports { source, .. } => source
+        }
+    }
+
+    pub fn imported(&self) -> &[String] {
+        match self {
+            ModuleSpecifier::Source(_) => &[],
+            ModuleSpecifier::Imports { imported, .. } => imported
+        }
+    }
+}
+
+impl From<&str> for ModuleSpecifier {
+    fn from(source: &str) -> Self {
+        ModuleSpecifier::Source(source.into())
+    }
+}
+
+impl From<String> for ModuleSpecifier {
+    fn from(source: String) -> Self {
+        ModuleSpecifier::Source(source)
+    }
+}
+
+/// How instrumented code passes assertion code and positions to the runtime.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// `_power_(callee, receiver, content)` per assertion and `tap(value, markerPos, startPos, endPos)`
+    #[default]
+    Inline,
+    /// one module-level table of assertion code and positions, referenced by index as in `tap(value, 2)`
+    Compact
+}
+
+/// Regular expression of names of functions to be instrumented, such as `^assert[A-Z]`.
+///
+/// Compiled when the options are deserialized or built, so that an invalid pattern is an error of the options.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CalleePattern(Regex);
+
+impl CalleePattern {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(CalleePattern)
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub(crate) fn regex(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for CalleePattern {
+    type Error = regex::Error;
+
+    fn try_from(pattern: String) -> Result<Self, Self::Error> {
+        CalleePattern::new(&pattern)
+    }
+}
+
+impl PartialEq for CalleePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for CalleePattern {}
+
+/// Options of the transform.
+///
+/// Deserialized from the plugin options in `.swcrc` (keys are camelCase),
+/// or built with [`Config::builder`] when embedding the transform natively.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct Config {
+    /// module specifier to import `_power_` from
+    pub runtime: String,
+    /// modules whose imports are treated as assertion functions
+    pub modules: Vec<ModuleSpecifier>,
+    /// protocol between instrumented code and the runtime
+    pub protocol: Protocol,
+    /// emit the node kind and the parent capture of each capture, so that the runtime can render the expression tree
+    pub expression_tree: bool,
+    /// evaluate side-effect-free assertions uninstrumented first, and re-run the instrumented copy only on failure
+    pub fast_path: bool,
+    /// warn about assignments, updates, `delete` and calls to mutating methods in assertion arguments
+    pub warn_side_effects: bool,
+    /// capture literals such as `1`, `'foo'` and `/re/` (operands of `===` and `==` in assertions are captured anyway)
+    pub capture_literals: bool,
+    /// capture `this`
+    pub capture_this: bool,
+    /// indexes of instrumented arguments per module and imported name, overriding the built-in table (e.g. `{ "node:assert": { "ok": [0] } }`)
+    pub captured_arguments: BTreeMap<String, BTreeMap<String, Vec<usize>>>,
+    /// instrument `expect(actual).<matchers...>(expected)` chains of `expect` imported from `vitest` or `@jest/globals`
+    pub expect: bool,
+    /// assertion libraries to be instrumented in addition to `modules`
+    pub presets: Vec<Preset>,
+    /// global identifiers or member paths to be instrumented, such as `assert` and `console.assert`
+    pub globals: Vec<String>,
+    /// regular expressions of names of imported or declared functions to be instrumented, such as `^assert[A-Z]`
+    pub callee_patterns: Vec<CalleePattern>
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            runtime: "@power-assert/runtime".into(),
+            modules: vec![
+                "node:assert".into(),
+                "node:assert/strict".into(),
+                "assert".into(),
+                "assert/strict".into(),
+                ModuleSpecifier::with_imports("vitest", ["assert"])
+            ],
+            protocol: Protocol::default(),
+            expression_tree: false,
+            fast_path: false,
+            warn_side_effects: false,
+            capture_literals: true,
+            capture_this: true,
+            captured_arguments: BTreeMap::new(),
+            expect: false,
+            presets: Vec::new(),
+            globals: Vec::new(),
+            callee_patterns: Vec::new()
+        }
+    }
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder for [`Config`], starting from the defaults.
+///
+/// ```
+/// use swc_plugin_power_assert::{Config, ModuleSpecifier};
+///
+/// let config = Config::builder()
+///     .runtime("./runtime.mjs")
+///     .module(ModuleSpecifier::with_imports("my-assert", ["ok", "equal"]))
+///     .build();
+/// assert_eq!(config.runtime, "./runtime.mjs");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config
+}
+
+impl ConfigBuilder {
+    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.config.runtime = runtime.into();
+        self
+    }
+
+    /// Replaces the target modules.
+    pub fn modules<M: Into<ModuleSpecifier>>(mut self, modules: impl IntoIterator<Item = M>) -> Self {
+        self.config.modules = modules.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds a target module in addition to the current ones.
+    pub fn module(mut self, module: impl Into<ModuleSpecifier>) -> Self {
+        self.config.modules.push(module.into());
+        self
+    }
+
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.config.protocol = protocol;
+        self
+    }
+
+    pub fn expression_tree(mut self, expression_tree: bool) -> Self {
+        self.config.expression_tree = expression_tree;
+        self
+    }
+
+    pub fn fast_path(mut self, fast_path: bool) -> Self {
+        self.config.fast_path = fast_path;
+        self
+    }
+
+    pub fn warn_side_effects(mut self, warn_side_effects: bool) -> Self {
+        self.config.warn_side_effects = warn_side_effects;
+        self
+    }
+
+    pub fn capture_literals(mut self, capture_literals: bool) -> Self {
+        self.config.capture_literals = capture_literals;
+        self
+    }
+
+    pub fn capture_this(mut self, capture_this: bool) -> Self {
+        self.config.capture_this = capture_this;
+        self
+    }
+
+    /// Sets indexes of arguments to be instrumented for the function imported from the module, `default` for its default export.
+    pub fn captured_arguments(mut self, module: impl Into<String>, imported: impl Into<String>, indexes: impl IntoIterator<Item = usize>) -> Self {
+        self.config.captured_arguments.entry(module.into()).or_default().insert(imported.into(), indexes.into_iter().collect());
+        self
+    }
+
+    pub fn expect(mut self, expect: bool) -> Self {
+        self.config.expect = expect;
+        self
+    }
+
+    /// Adds a preset in addition to the current ones.
+    pub fn preset(mut self, preset: Preset) -> Self {
+        self.config.presets.push(preset);
+        self
+    }
+
+    /// Adds a global identifier or member path, such as `console.assert`.
+    pub fn global(mut self, global: impl Into<String>) -> Self {
+        self.config.globals.push(global.into());
+        self
+    }
+
+    /// Adds a regular expression of names of functions to be instrumented, such as `^assert[A-Z]`.
+    pub fn callee_pattern(mut self, pattern: CalleePattern) -> Self {
+        self.config.callee_patterns.push(pattern);
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CalleePattern, Config, ModuleSpecifier, Preset, Protocol};
+
+    #[test]
+    fn test_deserialize_empty_options_as_default() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_deserialize_options() {
+        let config: Config = serde_json::from_str(r#"{
+            "runtime": "./runtime.mjs",
+            "modules": ["assert", { "source": "vitest", "imported": ["assert", "expect"] }],
+            "protocol": "compact",
+            "expressionTree": true,
+            "fastPath": true,
+            "warnSideEffects": true,
+            "captureLiterals": false,
+            "captureThis": false,
+            "capturedArguments": { "node:assert": { "ok": [0] }, "./my-assert.js": { "default": [0, 2] } },
+            "expect": true,
+            "presets": ["chai", "uvu/assert", "@std/assert", "bun:test", "power-assert", "invariant"],
+            "globals": ["assert", "console.assert"],
+            "calleePatterns": ["^assert[A-Z]"]
+        }"#).unwrap();
+        assert_eq!(config, Config::builder()
+            .runtime("./runtime.mjs")
+            .modules(["assert"])
+            .module(ModuleSpecifier::with_imports("vitest", ["assert", "expect"]))
+            .protocol(Protocol::Compact)
+            .expression_tree(true)
+            .fast_path(true)
+            .warn_side_effects(true)
+            .capture_literals(false)
+            .capture_this(false)
+            .captured_arguments("node:assert", "ok", [0])
+            .captured_arguments("./my-assert.js", "default", [0, 2])
+            .expect(true)
+            .preset(Preset::Chai)
+            .preset(Preset::Uvu)
+            .preset(Preset::StdAssert)
+            .preset(Preset::BunTest)
+            .preset(Preset::PowerAssert)
+            .preset(Preset::Invariant)
+            .global("assert")
+            .global("console.assert")
+            .callee_pattern(CalleePattern::new("^assert[A-Z]").unwrap())
+            .build());
+    }
+
+    #[test]
+    fn test_reject_unknown_options() {
+        assert!(serde_json::from_str::<Config>(r#"{ "module": ["assert"] }"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{ "presets": ["jasmine"] }"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{ "capturedArguments": { "ok": [0] } }"#).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_callee_patterns() {
+        let err = serde_json::from_str::<Config>(r#"{ "calleePatterns": ["^assert("] }"#).unwrap_err();
+        assert!(err.to_string().contains("unclosed group"), "{}", err);
+        assert!(CalleePattern::new("^assert(").is_err());
+    }
+}
@@ -1,4 +1,8 @@
-use bytes_str::BytesStr;
+mod config;
+mod preset;
+
+use std::fmt;
+use regex::Regex;
 use rustc_hash::{
     FxHashSet,
     FxHashMap
@@ -20,10 +24,10 @@ use swc_core::ecma::ast::{
     ExprOrSpread,
     Pat,
     Decl,
+    FnDecl,
     VarDecl,
     VarDeclKind,
     VarDeclarator,
-    Module,
     ModuleItem,
     ModuleDecl,
     ImportDecl,
@@ -33,6 +37,7 @@ use swc_core::ecma::ast::{
     ImportNamedSpecifier,
     ModuleExportName,
     MemberExpr,
+    ParenExpr,
     MemberProp,
     ComputedPropName,
     AssignExpr,
@@ -48,26 +53,77 @@ use swc_core::ecma::ast::{
     KeyValueProp,
     PropName,
     Function,
-    Callee
+    Callee,
+    ArrayLit,
+    ArrowExpr,
+    BlockStmtOrExpr,
+    OptChainExpr,
+    OptChainBase,
+    ExprStmt,
+    IfStmt,
+    MetaPropExpr,
+    MetaPropKind,
+    ObjectPat,
+    ObjectPatProp,
+    KeyValuePatProp,
+    AssignPatProp,
+    BindingIdent,
+    TsImportEqualsDecl,
+    TsModuleRef,
+    Pass
 };
-use swc_core::ecma::atoms::Atom;
+use swc_core::ecma::ast::EsVersion;
+use swc_core::ecma::atoms::{Atom, Wtf8Atom};
 use swc_core::ecma::visit::{
     visit_mut_pass,
+    Visit,
+    VisitWith,
     VisitMut,
     VisitMutWith
 };
+use swc_core::ecma::codegen::{
+    text_writer::JsWriter,
+    Emitter,
+    Node
+};
+use swc_core::ecma::parser::{
+    lexer::Lexer,
+    EsSyntax,
+    Parser,
+    StringInput,
+    Syntax
+};
 use swc_core::common::{
+    sync::Lrc,
+    BytePos,
+    Mark,
+    SourceMap,
+    SourceMapper,
     Span,
     Spanned
 };
+use swc_core::common::errors::HANDLER;
 use swc_core::common::source_map::SmallPos;
 use swc_core::common::util::take::Take;
+#[cfg(feature = "plugin")]
 use swc_core::plugin::plugin_transform;
+#[cfg(feature = "plugin")]
 use swc_core::plugin::metadata::{
     TransformPluginProgramMetadata,
     TransformPluginMetadataContextKind
 };
 
+pub use config::{
+    CalleePattern,
+    Config,
+    ConfigBuilder,
+    ModuleSpecifier,
+    Protocol
+};
+pub use preset::Preset;
+
+const ASSERTION_TABLE_VAR_NAME: &str = "_patbl";
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct Utf8Pos(u32);
 
@@ -118,25 +174,135 @@ impl SmallPos for Utf16Pos {
     }
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum SkipReason {
+    DummySpan,
+    SpanOutOfAssertion,
+    MarkerNotFound,
+    CodeNotRegenerated,
+    Script
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::DummySpan => write!(f, "the assertion contains a node without source position"),
+            SkipReason::SpanOutOfAssertion => write!(f, "a node span lies outside of the assertion"),
+            SkipReason::MarkerNotFound => write!(f, "an operator to place the marker on was not found"),
+            SkipReason::CodeNotRegenerated => write!(f, "the assertion code could not be regenerated from the AST"),
+            SkipReason::Script => write!(f, "the file is parsed as a script, which cannot import the runtime")
+        }
+    }
+}
+
 #[derive(Debug)]
 struct AssertionMetadata {
     ident_name: Atom,
     callee_ident_name: Atom,
-    receiver_ident_name: Option<Atom>,
+    // `assert` of `assert.equal`, or `vt.assert` of `vt.assert.equal`
+    receiver: Option<Box<Expr>>,
     assertion_code: String,
     assertion_start_pos: Utf8Pos,
-    contains_multibyte_char: bool,
+    utf16_pos_index: Utf16PosIndex,
+    regenerated_pos_map: Option<FxHashMap<BytePos, Utf8Pos>>,
+    binary_op: Option<String>,
+    skip_reason: Option<SkipReason>,
+    // markerPos, startPos and endPos of each capture point, flattened (compact protocol only)
+    capture_positions: Vec<u32>,
+    table_index: Option<usize>,
+    // capture points are indexed in pre-order, so that children know the index of their parent
+    capture_count: u32,
+    enclosing_captures: Vec<u32>,
+    // left and right of `===` or `==` right under the assertion, captured regardless of the capture policy for hints
+    binexp_operand_spans: Vec<Span>
+}
+
+impl AssertionMetadata {
+    fn relative_utf8_pos(&self, pos: BytePos) -> Result<Utf8Pos, SkipReason> {
+        if pos.is_dummy() {
+            return Err(SkipReason::DummySpan);
+        }
+        let relative_pos = match &self.regenerated_pos_map {
+            // assertion code is regenerated, so positions are looked up through the map
+            Some(pos_map) => pos_map.get(&pos).cloned().ok_or(SkipReason::SpanOutOfAssertion)?,
+            None => Utf8Pos(pos.to_u32().checked_sub(self.assertion_start_pos.to_u32()).ok_or(SkipReason::SpanOutOfAssertion)?)
+        };
+        if relative_pos.to_usize() > self.assertion_code.len() {
+            return Err(SkipReason::SpanOutOfAssertion);
+        }
+        Ok(relative_pos)
+    }
+
+    fn find_token(&self, token_str: &str, search_start_pos: Utf8Pos) -> Option<Utf8Pos> {
+        let search_start_usize = search_start_pos.to_usize();
+        let search_target_code = self.assertion_code.get(search_start_usize..)?;
+        // lex the code instead of searching for the substring, so that comments and string literals are skipped
+        let start_pos = BytePos(1);
+        let end_pos = start_pos + BytePos(search_target_code.len() as u32);
+        let lexer = Lexer::new(Syntax::Typescript(Default::default()), EsVersion::latest(), StringInput::new(search_target_code, start_pos, end_pos), None);
+        lexer
+            .map(|token_and_span| token_and_span.span)
+            .map(|span| ((span.lo - start_pos).to_usize(), (span.hi - start_pos).to_usize()))
+            .find(|(lo, hi)| search_target_code.get(*lo..*hi) == Some(token_str))
+            .map(|(lo, _)| Utf8Pos((search_start_usize + lo) as u32))
+    }
+}
+
+// an entry of the module-level assertion table of the compact protocol
+#[derive(Debug)]
+struct AssertionTableEntry {
+    assertion_code: String,
+    capture_positions: Vec<u32>,
     binary_op: Option<String>
 }
 
+#[derive(Debug)]
+struct RegeneratedCode {
+    code: String,
+    pos_map: FxHashMap<BytePos, Utf8Pos>
+}
+
 #[derive(Debug)]
 struct ArgumentMetadata {
     is_captured: bool,
+    is_spread: bool,
     ident_name: Atom,
     arg_index: usize,
     powered_ident_name: Atom
 }
 
+// UTF-8 and UTF-16 positions right after each non-ASCII char, so that
+// positions in between can be calculated from the nearest preceding one
+#[derive(Debug)]
+struct Utf16PosIndex {
+    checkpoints: Vec<(Utf8Pos, Utf16Pos)>
+}
+
+impl Utf16PosIndex {
+    fn new(code: &str) -> Self {
+        let mut checkpoints = Vec::new();
+        let mut utf16_pos = 0;
+        for (utf8_pos, c) in code.char_indices() {
+            utf16_pos += c.len_utf16();
+            if !c.is_ascii() {
+                checkpoints.push((Utf8Pos::from_usize(utf8_pos + c.len_utf8()), Utf16Pos::from_usize(utf16_pos)));
+            }
+        }
+        Utf16PosIndex {
+            checkpoints
+        }
+    }
+
+    fn lookup(&self, utf8_pos: Utf8Pos) -> Utf16Pos {
+        // chars between the nearest preceding checkpoint and the position are all ASCII
+        let idx = self.checkpoints.partition_point(|(checkpoint_utf8_pos, _)| checkpoint_utf8_pos.to_u32() <= utf8_pos.to_u32());
+        match idx.checked_sub(1).map(|i| &self.checkpoints[i]) {
+            Some((checkpoint_utf8_pos, checkpoint_utf16_pos)) => Utf16Pos(checkpoint_utf16_pos.to_u32() + (utf8_pos.to_u32() - checkpoint_utf8_pos.to_u32())),
+            None => Utf16Pos(utf8_pos.to_u32())
+        }
+    }
+}
+
 #[derive(Debug)]
 struct AssertionRelativeOffset {
     start_pos: Utf16Pos,
@@ -144,113 +310,179 @@ struct AssertionRelativeOffset {
     marker_pos: Utf16Pos
 }
 
+struct CapturePoint {
+    index: u32,
+    parent: Option<u32>,
+    kind: &'static str,
+    offset: AssertionRelativeOffset
+}
+
+// capture of the outer assertion, put aside while capturing assertions inside callbacks passed to it
+struct SuspendedCapture {
+    assertion_metadata: Option<AssertionMetadata>,
+    argument_metadata: Option<ArgumentMetadata>,
+    do_not_capture_immediate_child: bool,
+    arrow_depth: usize
+}
+
+// where a target variable comes from, by which the argument roles of its calls are looked up
+#[derive(Debug, Clone)]
+struct TargetBinding {
+    // None for functions declared in the program, such as assertUser matched by callee patterns
+    module: Option<Atom>,
+    // `default` for default imports and `*` for namespace imports
+    imported: Atom
+}
+
+impl TargetBinding {
+    fn imported_from(src: &Str, imported: Atom) -> Self {
+        TargetBinding {
+            module: Some(src.value.to_atom_lossy().into_owned()),
+            imported
+        }
+    }
+}
+
+const MATCHER_CHAIN_EXPECT_PARAM_NAME: &str = "_paexpect";
+
+// module of import.meta.vitest for in-source testing
+const VITEST_MODULE: &str = "vitest";
+
+// modules whose expect is instrumented with the expect option
+const EXPECT_MODULES: [&str; 2] = ["vitest", "@jest/globals"];
+
 pub struct TransformVisitor {
-    span_offset: u32,
     powered_var_cnt: usize,
     argrec_var_cnt: usize,
-    target_variables: FxHashSet<Id>,
+    // with the module and the name they are imported by, e.g. (node:assert, strictEqual) for `import { strictEqual as eq }`
+    target_variables: FxHashMap<Id, TargetBinding>,
+    // namespace and default imports of modules with allowlists, whose members are checked against them
+    target_namespaces: FxHashMap<Id, (Atom, FxHashSet<Atom>)>,
     target_modules: FxHashMap<Atom, FxHashSet<Atom>>,
+    // globs of module specifiers such as **/test-utils/*.js, with their allowlists
+    target_module_patterns: Vec<(Regex, FxHashSet<Atom>)>,
+    // names of imported or declared functions to be instrumented, such as ^assert[A-Z]
+    callee_patterns: Vec<Regex>,
+    // paths of global assertions such as [console, assert], matched only when unresolved
+    globals: Vec<Vec<Atom>>,
+    unresolved_mark: Option<Mark>,
+    // expect imported from expect_modules, whose matcher chains are instrumented
+    expect_variables: FxHashSet<Id>,
+    expect_modules: FxHashSet<Atom>,
     assertion_metadata_vec: Vec<AssertionMetadata>,
     assertion_metadata: Option<AssertionMetadata>,
     argument_metadata_vec: Vec<ArgumentMetadata>,
     argument_metadata: Option<ArgumentMetadata>,
     is_runtime_imported: bool,
     do_not_capture_immediate_child: bool,
-    code: BytesStr
+    // depth of arrow functions with expression bodies inside the current argument
+    arrow_depth: usize,
+    runtime: Atom,
+    protocol: Protocol,
+    expression_tree: bool,
+    capture_policy: CapturePolicy,
+    fast_path: bool,
+    warn_side_effects: bool,
+    assertion_table: Vec<AssertionTableEntry>,
+    source_map: Option<Lrc<dyn SourceMapper>>,
+    filename: Option<String>
 }
 
 impl Default for TransformVisitor {
     fn default() -> Self {
+        TransformVisitor::new(&Config::default())
+    }
+}
+
+impl TransformVisitor {
+    pub fn new(config: &Config) -> Self {
         let mut visitor = TransformVisitor {
-            span_offset: 0,
             powered_var_cnt: 0,
             argrec_var_cnt: 0,
-            target_variables: FxHashSet::default(),
+            target_variables: FxHashMap::default(),
+            target_namespaces: FxHashMap::default(),
             target_modules: FxHashMap::default(),
+            target_module_patterns: Vec::new(),
+            callee_patterns: config.callee_patterns.iter().map(|pattern| pattern.regex().clone()).collect(),
+            globals: config.globals.iter().map(|global| global.split('.').map(Atom::from).collect()).collect(),
+            unresolved_mark: None,
+            expect_variables: FxHashSet::default(),
+            expect_modules: FxHashSet::default(),
             assertion_metadata_vec: Vec::new(),
             assertion_metadata: None,
             argument_metadata_vec: Vec::new(),
             argument_metadata: None,
             do_not_capture_immediate_child: false,
+            arrow_depth: 0,
             is_runtime_imported: false,
-            code: BytesStr::from("")
+            runtime: Atom::from(config.runtime.as_str()),
+            protocol: config.protocol,
+            expression_tree: config.expression_tree,
+            capture_policy: CapturePolicy::new(config),
+            fast_path: config.fast_path,
+            warn_side_effects: config.warn_side_effects,
+            assertion_table: Vec::new(),
+            source_map: None,
+            filename: None
         };
-        for module_name in [
-            "node:assert",
-            "node:assert/strict",
-            "assert",
-            "assert/strict",
-        ].iter() {
-            visitor.target_modules.insert(Atom::from(*module_name), FxHashSet::default());
-        }
-        {
-            // allowlist for vitest
-            let mut vitest_allowlist = FxHashSet::default();
-            vitest_allowlist.insert(Atom::from("assert"));
-            visitor.target_modules.insert(Atom::from("vitest"), vitest_allowlist);
+        let preset_modules = config.presets.iter().flat_map(|preset| preset.modules());
+        for module in config.modules.iter().cloned().chain(preset_modules) {
+            // empty allowlist means that every import from the module is a target
+            let imported = module.imported().iter().map(|imported| Atom::from(imported.as_str()));
+            if is_glob(module.source()) {
+                visitor.target_module_patterns.push((glob_to_regex(module.source()), imported.collect()));
+            } else {
+                visitor.target_modules.entry(Atom::from(module.source())).or_default().extend(imported);
+            }
+        }
+        if config.expect {
+            visitor.expect_modules.extend(EXPECT_MODULES.into_iter().map(Atom::from));
+        }
+        for preset in config.presets.iter() {
+            visitor.expect_modules.extend(preset.expect_modules().iter().copied().map(Atom::from));
         }
         visitor
     }
 }
 
-// /cwd is the root of sandbox
-// https://github.com/swc-project/swc/discussions/4997
-fn resolve_path_in_sandbox(filename: &String, cwd_str: &String) -> String {
-    if filename.starts_with("file://") {
-        let abs_path_like = filename.replace("file://", "");
-        return resolve_path_in_sandbox(&abs_path_like, cwd_str);
-    }
-    if filename.starts_with(cwd_str) {
-        let relative_path_from_cwd = filename.replace(cwd_str, "");
-        return format!("/cwd{}", relative_path_from_cwd);
-    }
-    format!("/cwd/{}", filename)
-}
-
-impl From<&String> for TransformVisitor {
-    fn from(code: &String) -> Self {
+impl From<Lrc<SourceMap>> for TransformVisitor {
+    fn from(source_map: Lrc<SourceMap>) -> Self {
         TransformVisitor {
-            code: BytesStr::from(code.clone()),
+            source_map: Some(source_map),
             .. Default::default()
         }
     }
 }
 
-impl From<TransformPluginProgramMetadata> for TransformVisitor {
-    fn from(metadata: TransformPluginProgramMetadata) -> Self {
-        let code = match metadata.source_map.source_file.get() {
-            Some(source_file) => {
-                source_file.src.clone()
-            },
-            None => {
-                let filename = metadata
-                    .get_context(&TransformPluginMetadataContextKind::Filename)
-                    .expect("filename should exist");
-                // println!("filename: {:?}", filename);
-
-                let cwd = metadata
-                    .get_context(&TransformPluginMetadataContextKind::Cwd)
-                    .expect("cwd should exist");
-                // println!("cwd: {:?}", cwd);
-
-                // let env = metadata
-                //     .get_context(&TransformPluginMetadataContextKind::Env)
-                //     .expect("env should exist");
-                // println!("env: {:?}", env);  // "development"
-
-                // /cwd is the root of sandbox
-                // https://github.com/swc-project/swc/discussions/4997
-                let path_in_sandbox = resolve_path_in_sandbox(&filename, &cwd);
-                // println!("path_in_sandbox: {:?}", path_in_sandbox);
-                // read all file contens into string
-                let error_message = format!("failed to read file: {}", path_in_sandbox);
-                let code = std::fs::read_to_string(path_in_sandbox).expect(&error_message);
-                BytesStr::from(code)
+#[cfg(feature = "plugin")]
+impl TransformVisitor {
+    /// Returns None when the options are invalid, which are reported through the handler instead of panicking in the host.
+    fn from_plugin_metadata(metadata: TransformPluginProgramMetadata) -> Option<Self> {
+        let config = plugin_config(metadata.get_transform_plugin_config().as_deref())?;
+        let filename = metadata.get_context(&TransformPluginMetadataContextKind::Filename);
+        Some(TransformVisitor {
+            source_map: Some(Lrc::new(metadata.source_map)),
+            filename,
+            unresolved_mark: Some(metadata.unresolved_mark),
+            .. TransformVisitor::new(&config)
+        })
+    }
+}
+
+#[cfg(feature = "plugin")]
+fn plugin_config(config_json: Option<&str>) -> Option<Config> {
+    let Some(config_json) = config_json else {
+        return Some(Config::default());
+    };
+    match serde_json::from_str(config_json) {
+        Ok(config) => Some(config),
+        Err(err) => {
+            if HANDLER.is_set() {
+                HANDLER.with(|handler| {
+                    handler.err(&format!("power-assert: invalid options for swc-plugin-power-assert: {}", err));
+                });
             }
-        };
-        TransformVisitor {
-            code,
-            .. Default::default()
+            None
         }
     }
 }
@@ -275,6 +507,31 @@ impl TransformVisitor {
         self.argument_metadata_vec.clear();
     }
 
+    fn take_transformations(&mut self) -> (Vec<AssertionMetadata>, Vec<ArgumentMetadata>) {
+        (std::mem::take(&mut self.assertion_metadata_vec), std::mem::take(&mut self.argument_metadata_vec))
+    }
+
+    fn restore_transformations(&mut self, (assertion_metadata_vec, argument_metadata_vec): (Vec<AssertionMetadata>, Vec<ArgumentMetadata>)) {
+        self.assertion_metadata_vec = assertion_metadata_vec;
+        self.argument_metadata_vec = argument_metadata_vec;
+    }
+
+    fn suspend_capture(&mut self) -> SuspendedCapture {
+        SuspendedCapture {
+            assertion_metadata: self.assertion_metadata.take(),
+            argument_metadata: self.argument_metadata.take(),
+            do_not_capture_immediate_child: std::mem::take(&mut self.do_not_capture_immediate_child),
+            arrow_depth: std::mem::take(&mut self.arrow_depth)
+        }
+    }
+
+    fn resume_capture(&mut self, suspended: SuspendedCapture) {
+        self.assertion_metadata = suspended.assertion_metadata;
+        self.argument_metadata = suspended.argument_metadata;
+        self.do_not_capture_immediate_child = suspended.do_not_capture_immediate_child;
+        self.arrow_depth = suspended.arrow_depth;
+    }
+
     fn has_declarations_to_be_inserted(&mut self) -> bool {
         !self.assertion_metadata_vec.is_empty() || !self.argument_metadata_vec.is_empty()
     }
@@ -294,22 +551,16 @@ impl TransformVisitor {
     }
 
     fn apply_to_tap_if_exists_directly_under_the_current_node(&self, expr: &mut Box<Expr>, argrec_ident_name: &Atom, f: &dyn Fn(&mut Vec<ExprOrSpread>, &mut IdentName)) -> bool {
-        match expr.as_mut() {
-            Expr::Call(CallExpr { callee: Callee::Expr(callee), args, .. }) => {
-                match callee.as_mut() {
-                    Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop_ident), .. }) => {
-                        match obj.as_ref() {
-                            Expr::Ident(obj_ident) if obj_ident.sym == *argrec_ident_name && prop_ident.sym == "tap" => {
-                                f(args, prop_ident);
-                                return true;
-                            },
-                            _ => {}
-                        }
+        if let Expr::Call(CallExpr { callee: Callee::Expr(callee), args, .. }) = expr.as_mut() {
+            if let Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop_ident), .. }) = callee.as_mut() {
+                match obj.as_ref() {
+                    Expr::Ident(obj_ident) if obj_ident.sym == *argrec_ident_name && prop_ident.sym == "tap" => {
+                        f(args, prop_ident);
+                        return true;
                     },
                     _ => {}
                 }
-            },
-            _ => {}
+            }
         }
         false
     }
@@ -332,16 +583,28 @@ impl TransformVisitor {
         })
     }
 
+    fn add_hint(&self, tap_args: &mut Vec<ExprOrSpread>, hint: &str) {
+        // merge into the metadata object of the expression tree if exists
+        if let Some(Expr::Object(metadata)) = tap_args.iter_mut().skip(1).last().map(|arg| arg.expr.as_mut()) {
+            let Expr::Object(hint_object) = self.create_hint_object(hint) else {
+                unreachable!();
+            };
+            metadata.props.extend(hint_object.props);
+            return;
+        }
+        tap_args.push(ExprOrSpread::from(Box::new(self.create_hint_object(hint))));
+    }
+
     fn apply_binexp_hint(&self, arg: &mut ExprOrSpread, argrec_ident_name: &Atom) {
         self.apply_to_tap_if_exists_directly_under_the_current_node(&mut arg.expr, argrec_ident_name, &|args, _prop_ident| {
             let value = &mut args[0];
             // let mut pos = &args[1];
             if let Expr::Bin(BinExpr { left, right, .. }) = value.expr.as_mut() {
                 self.apply_to_tap_if_exists_directly_under_the_current_node(left, argrec_ident_name, &|args, _prop_ident| {
-                    args.push(ExprOrSpread::from(Box::new(self.create_hint_object("left"))));
+                    self.add_hint(args, "left");
                 });
                 self.apply_to_tap_if_exists_directly_under_the_current_node(right, argrec_ident_name, &|args, _prop_ident| {
-                    args.push(ExprOrSpread::from(Box::new(self.create_hint_object("right"))));
+                    self.add_hint(args, "right");
                 });
             };
         });
@@ -365,7 +628,62 @@ impl TransformVisitor {
         });
     }
 
-    fn wrap_with_tap(&mut self, expr: &mut Expr, assertion_relative_offset: &AssertionRelativeOffset) {
+    fn start_capture(&mut self, expr: &Expr, offset: AssertionRelativeOffset) -> CapturePoint {
+        let assertion_metadata = self.assertion_metadata.as_mut().unwrap();
+        let index = assertion_metadata.capture_count;
+        assertion_metadata.capture_count += 1;
+        let parent = assertion_metadata.enclosing_captures.last().copied();
+        assertion_metadata.enclosing_captures.push(index);
+        if self.protocol == Protocol::Compact {
+            // positions go to the assertion table, and the capture point is referred by its index
+            assertion_metadata.capture_positions.extend([
+                offset.marker_pos.to_u32(),
+                offset.start_pos.to_u32(),
+                offset.end_pos.to_u32()
+            ]);
+        }
+        CapturePoint {
+            index,
+            parent,
+            kind: estree_node_type(expr),
+            offset
+        }
+    }
+
+    // { id: 1, kind: "Identifier", parent: 0 }, id is omitted in compact protocol since the capture index is passed already
+    fn create_expression_tree_object(&self, capture_point: &CapturePoint) -> Expr {
+        let mut props: Vec<(&str, Lit)> = Vec::new();
+        if self.protocol == Protocol::Inline {
+            props.push(("id", Lit::Num(Number::from(capture_point.index as f64))));
+        }
+        props.push(("kind", Lit::Str(capture_point.kind.into())));
+        if let Some(parent) = capture_point.parent {
+            props.push(("parent", Lit::Num(Number::from(parent as f64))));
+        }
+        Expr::Object(ObjectLit{
+            span: Span::default(),
+            props: props.into_iter().map(|(key, value)| {
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key.into()),
+                    value: Box::new(Expr::Lit(value))
+                })))
+            }).collect()
+        })
+    }
+
+    fn wrap_with_tap(&mut self, expr: &mut Expr, capture_point: &CapturePoint) {
+        self.assertion_metadata.as_mut().unwrap().enclosing_captures.pop();
+        let mut tap_args: Vec<ExprOrSpread> = match self.protocol {
+            Protocol::Inline => vec![
+                capture_point.offset.marker_pos.to_u32(),
+                capture_point.offset.start_pos.to_u32(),
+                capture_point.offset.end_pos.to_u32()
+            ],
+            Protocol::Compact => vec![capture_point.index]
+        }.into_iter().map(|num| ExprOrSpread::from(Box::new(Expr::Lit(Lit::Num(Number::from(num as f64)))))).collect();
+        if self.expression_tree {
+            tap_args.push(ExprOrSpread::from(Box::new(self.create_expression_tree_object(capture_point))));
+        }
         let arg_rec = self.argument_metadata.as_mut().unwrap();
         arg_rec.is_captured = true;
         let argrec_ident_name = &arg_rec.ident_name;
@@ -379,69 +697,61 @@ impl TransformVisitor {
                         ..Default::default()
                     }
                 ))),
-                args: vec![
-                    ExprOrSpread::from(Box::new(ex)),
-                    ExprOrSpread::from(Box::new(Expr::Lit(Lit::Num(Number::from(assertion_relative_offset.marker_pos.to_u32() as f64))))),
-                    ExprOrSpread::from(Box::new(Expr::Lit(Lit::Num(Number::from(assertion_relative_offset.start_pos.to_u32() as f64))))),
-                    ExprOrSpread::from(Box::new(Expr::Lit(Lit::Num(Number::from(assertion_relative_offset.end_pos.to_u32() as f64)))))
-                ],
+                args: [ExprOrSpread::from(Box::new(ex))].into_iter().chain(tap_args).collect(),
                 ..Default::default()
             })
         });
     }
 
-    fn calculate_utf16_assertion_relative_offset(&self, expr: &Expr) -> AssertionRelativeOffset {
+    fn calculate_utf16_assertion_relative_offset(&self, expr: &Expr) -> Result<AssertionRelativeOffset, SkipReason> {
         let assertion_metadata = self.assertion_metadata.as_ref().unwrap();
-        let assertion_start_pos = &assertion_metadata.assertion_start_pos;
-        let marker_pos_utf8 = self.calculate_utf8_marker_pos(expr, assertion_start_pos);
-        let start_pos_utf8 = Utf8Pos(expr.span_lo().to_u32() - assertion_start_pos.to_u32());
-        let end_pos_utf8 = Utf8Pos(expr.span_hi().to_u32() - assertion_start_pos.to_u32());
-        AssertionRelativeOffset {
-            start_pos: to_utf16_pos(assertion_metadata, start_pos_utf8),
-            end_pos: to_utf16_pos(assertion_metadata, end_pos_utf8),
-            marker_pos: to_utf16_pos(assertion_metadata, marker_pos_utf8)
-        }
+        let marker_pos_utf8 = self.calculate_utf8_marker_pos(expr, assertion_metadata)?;
+        let start_pos_utf8 = assertion_metadata.relative_utf8_pos(expr.span_lo())?;
+        let end_pos_utf8 = assertion_metadata.relative_utf8_pos(expr.span_hi())?;
+        Ok(AssertionRelativeOffset {
+            start_pos: to_utf16_pos(assertion_metadata, start_pos_utf8)?,
+            end_pos: to_utf16_pos(assertion_metadata, end_pos_utf8)?,
+            marker_pos: to_utf16_pos(assertion_metadata, marker_pos_utf8)?
+        })
     }
 
-    fn calculate_utf8_marker_pos(&self, expr: &Expr, assertion_start_pos: &Utf8Pos) -> Utf8Pos {
+    fn calculate_utf8_marker_pos(&self, expr: &Expr, assertion_metadata: &AssertionMetadata) -> Result<Utf8Pos, SkipReason> {
         match expr {
             Expr::Member(MemberExpr{ prop, .. }) => {
                 match prop {
-                    MemberProp::Computed(ComputedPropName{ span, .. }) => Utf8Pos(span.lo.to_u32() - assertion_start_pos.to_u32()),
-                    MemberProp::Ident(IdentName { span, .. }) => Utf8Pos(span.lo.to_u32() - assertion_start_pos.to_u32()),
-                    _ => Utf8Pos(expr.span_lo().to_u32() - assertion_start_pos.to_u32())
+                    MemberProp::Computed(ComputedPropName{ span, .. }) => assertion_metadata.relative_utf8_pos(span.lo),
+                    MemberProp::Ident(IdentName { span, .. }) => assertion_metadata.relative_utf8_pos(span.lo),
+                    _ => assertion_metadata.relative_utf8_pos(expr.span_lo())
                 }
             },
             Expr::Call(CallExpr{ callee: Callee::Expr(callee_expr), .. }) => {
                 match callee_expr.as_ref() {
                     // for callee like `foo()`, foo's span is used
-                    Expr::Ident(Ident { span, .. }) => Utf8Pos(span.lo.to_u32() - assertion_start_pos.to_u32()),
+                    Expr::Ident(Ident { span, .. }) => assertion_metadata.relative_utf8_pos(span.lo),
                     // for callee like `foo.bar()`, bar's span is used
-                    Expr::Member(MemberExpr{ prop: MemberProp::Ident(IdentName { span, .. }), .. }) => Utf8Pos(span.lo.to_u32() - assertion_start_pos.to_u32()),
+                    Expr::Member(MemberExpr{ prop: MemberProp::Ident(IdentName { span, .. }), .. }) => assertion_metadata.relative_utf8_pos(span.lo),
                     // otherwise, span of opening parenthesis is used
-                    _ => self.search_pos_for("(", &callee_expr.span(), assertion_start_pos)
+                    _ => self.search_pos_for("(", &callee_expr.span(), assertion_metadata)
                 }
             },
             // estree's LogicalExpression is mapped to BinaryExpression in swc
-            Expr::Bin(BinExpr{ left, op, ..}) => self.search_pos_for(op.as_str(), &left.span(), assertion_start_pos),
-            Expr::Assign(AssignExpr{ left, op, .. }) => self.search_pos_for(op.as_str(), &left.span(), assertion_start_pos),
-            Expr::Cond(CondExpr{ test, .. }) => self.search_pos_for("?", &test.span(), assertion_start_pos),
+            Expr::Bin(BinExpr{ left, op, ..}) => self.search_pos_for(op.as_str(), &left.span(), assertion_metadata),
+            Expr::Assign(AssignExpr{ left, op, .. }) => self.search_pos_for(op.as_str(), &left.span(), assertion_metadata),
+            Expr::Cond(CondExpr{ test, .. }) => self.search_pos_for("?", &test.span(), assertion_metadata),
             Expr::Update(UpdateExpr{ arg, op, prefix, .. }) => {
                 if *prefix {
-                    Utf8Pos(expr.span_lo().to_u32() - assertion_start_pos.to_u32())
+                    assertion_metadata.relative_utf8_pos(expr.span_lo())
                 } else {
-                    self.search_pos_for(op.as_str(), &arg.span(), assertion_start_pos)
+                    self.search_pos_for(op.as_str(), &arg.span(), assertion_metadata)
                 }
             },
-            _ => Utf8Pos(expr.span_lo().to_u32() - assertion_start_pos.to_u32())
+            _ => assertion_metadata.relative_utf8_pos(expr.span_lo())
         }
     }
 
-    fn search_pos_for(&self, search_target_str: &str, search_start_span: &Span, assertion_start_pos: &Utf8Pos) -> Utf8Pos {
-        let search_start_pos = search_start_span.hi.to_usize() - assertion_start_pos.to_usize();
-        let assertion_code: &String = &self.assertion_metadata.as_ref().unwrap().assertion_code;
-        let found = assertion_code[search_start_pos..].find(search_target_str).unwrap_or(0);
-        Utf8Pos((found + search_start_pos) as u32)
+    fn search_pos_for(&self, search_target_str: &str, search_start_span: &Span, assertion_metadata: &AssertionMetadata) -> Result<Utf8Pos, SkipReason> {
+        let search_start_pos = assertion_metadata.relative_utf8_pos(search_start_span.hi)?;
+        assertion_metadata.find_token(search_target_str, search_start_pos).ok_or(SkipReason::MarkerNotFound)
     }
 
     fn create_argrec_decl(&self, argument_metadata: &ArgumentMetadata) -> Stmt {
@@ -456,7 +766,8 @@ impl TransformVisitor {
                         callee: Callee::Expr(Box::new(Expr::Member(
                             MemberExpr {
                                 obj: Box::new(Expr::Ident(argument_metadata.powered_ident_name.clone().into())),
-                                prop: MemberProp::Ident("recorder".into()),
+                                // the runtime expands recorded values of spread arguments
+                                prop: MemberProp::Ident(if argument_metadata.is_spread { "spreadRecorder".into() } else { "recorder".into() }),
                                 ..Default::default()
                             }
                         ))),
@@ -472,14 +783,27 @@ impl TransformVisitor {
         })))
     }
 
+    fn create_binexp_object(&self, binary_op: &str) -> Expr {
+        // object expression { binexp: "===" }
+        Expr::Object(ObjectLit{
+            props: vec![
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident("binexp".into()),
+                    value: Box::new(Expr::Lit(Lit::Str(binary_op.into())))
+                })))
+            ],
+            ..Default::default()
+        })
+    }
+
     fn create_powered_runner_decl(&self, assertion_metadata: &AssertionMetadata) -> Stmt {
-        let mut args = vec![
+        let callee_and_receiver = [
             ExprOrSpread::from(Box::new(
-                match &assertion_metadata.receiver_ident_name {
-                    Some(receiver_ident_name) => {
+                match &assertion_metadata.receiver {
+                    Some(receiver) => {
                         Expr::Member(
                             MemberExpr {
-                                obj: Box::new(Expr::Ident(receiver_ident_name.clone().into())),
+                                obj: receiver.clone(),
                                 prop: MemberProp::Ident(assertion_metadata.callee_ident_name.clone().into()),
                                 ..Default::default()
                             }
@@ -491,30 +815,34 @@ impl TransformVisitor {
                 }
             )),
             ExprOrSpread::from(Box::new(
-                match &assertion_metadata.receiver_ident_name {
-                    Some(receiver_ident_name) => {
-                        Expr::Ident(receiver_ident_name.clone().into())
+                match &assertion_metadata.receiver {
+                    Some(receiver) => {
+                        *receiver.clone()
                     },
                     None => {
                         Expr::Lit(Lit::Null(Null { span: Span::default() }))
                     }
                 }
-            )),
-            ExprOrSpread::from(Box::new(Expr::Lit(Lit::Str(Str::from(assertion_metadata.assertion_code.clone())))))
+            ))
         ];
 
-        if assertion_metadata.binary_op.is_some() {
-            // add object expression { binexp: "===" } to args
-            args.push(ExprOrSpread::from(Box::new(Expr::Object(ObjectLit{
-                props: vec![
-                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                        key: PropName::Ident("binexp".into()),
-                        value: Box::new(Expr::Lit(Lit::Str(assertion_metadata.binary_op.as_ref().unwrap().clone().into())))
-                    })))
-                ],
-                ..Default::default()
-            }))));
-        }
+        let (runtime_fn_name, args): (&str, Vec<ExprOrSpread>) = match assertion_metadata.table_index {
+            // const _pasrt1 = _patbl(0, assert, null);
+            Some(table_index) => {
+                let mut args = vec![ExprOrSpread::from(Box::new(Expr::Lit(Lit::Num(Number::from(table_index as f64)))))];
+                args.extend(callee_and_receiver);
+                (ASSERTION_TABLE_VAR_NAME, args)
+            },
+            // const _pasrt1 = _power_(assert, null, "assert(truthy)");
+            None => {
+                let mut args = Vec::from(callee_and_receiver);
+                args.push(ExprOrSpread::from(Box::new(Expr::Lit(Lit::Str(Str::from(assertion_metadata.assertion_code.clone()))))));
+                if let Some(binary_op) = &assertion_metadata.binary_op {
+                    args.push(ExprOrSpread::from(Box::new(self.create_binexp_object(binary_op))));
+                }
+                ("_power_", args)
+            }
+        };
 
         Stmt::Decl(Decl::Var(Box::new(VarDecl {
             kind: VarDeclKind::Const,
@@ -525,7 +853,7 @@ impl TransformVisitor {
                     name: Pat::Ident(assertion_metadata.ident_name.clone().into()),
                     init: Some(Box::new(Expr::Call(CallExpr {
                         callee: Callee::Expr(Box::new(
-                            Expr::Ident("_power_".into())
+                            Expr::Ident(runtime_fn_name.into())
                         )),
                         args,
                         ..Default::default()
@@ -537,46 +865,294 @@ impl TransformVisitor {
         })))
     }
 
+    // const _patbl = _power_table_([["assert(truthy)", [7, 7, 13]], ...]);
+    fn create_assertion_table_decl(&self) -> Stmt {
+        let entries = self.assertion_table.iter().map(|entry| {
+            let mut elems = vec![
+                Expr::Lit(Lit::Str(Str::from(entry.assertion_code.clone()))),
+                Expr::Array(ArrayLit {
+                    elems: entry.capture_positions.iter().map(|pos| Some(ExprOrSpread::from(Box::new(Expr::Lit(Lit::Num(Number::from(*pos as f64))))))).collect(),
+                    ..Default::default()
+                })
+            ];
+            if let Some(binary_op) = &entry.binary_op {
+                elems.push(self.create_binexp_object(binary_op));
+            }
+            Some(ExprOrSpread::from(Box::new(Expr::Array(ArrayLit {
+                elems: elems.into_iter().map(|elem| Some(ExprOrSpread::from(Box::new(elem)))).collect(),
+                ..Default::default()
+            }))))
+        }).collect();
+        Stmt::Decl(Decl::Var(Box::new(VarDecl {
+            kind: VarDeclKind::Const,
+            declare: false,
+            decls: vec![
+                VarDeclarator {
+                    span: Span::default(),
+                    name: Pat::Ident(ASSERTION_TABLE_VAR_NAME.into()),
+                    init: Some(Box::new(Expr::Call(CallExpr {
+                        callee: Callee::Expr(Box::new(
+                            Expr::Ident("_power_table_".into())
+                        )),
+                        args: vec![
+                            ExprOrSpread::from(Box::new(Expr::Array(ArrayLit {
+                                elems: entries,
+                                ..Default::default()
+                            })))
+                        ],
+                        ..Default::default()
+                    }))),
+                    definite: false
+                }
+            ],
+            ..Default::default()
+        })))
+    }
+
     fn create_power_assert_runtime_import_decl(&mut self) -> ModuleItem {
         self.is_runtime_imported = true;
         ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
             span: Span::default(),
             specifiers: vec![
                 ImportSpecifier::Named(ImportNamedSpecifier {
-                    local: "_power_".into(),
+                    local: match self.protocol {
+                        Protocol::Inline => "_power_".into(),
+                        Protocol::Compact => "_power_table_".into()
+                    },
                     imported: None,
                     span: Span::default(),
                     is_type_only: false,
                 })
             ],
-            src: Box::new("@power-assert/runtime".into()),
+            src: Box::new(self.runtime.clone().into()),
             type_only: false,
             with: None,
             phase: Default::default()
         }))
     }
 
-    fn capture_assertion(&mut self, n: &mut CallExpr, prop_ident_name: Atom, obj_ident_name: Option<Atom>) {
+    fn create_fast_path_call(&self, powered_ident_name: &str, original: CallExpr, powered: CallExpr) -> CallExpr {
+        let thunk = |call: CallExpr| ExprOrSpread::from(Box::new(Expr::Arrow(ArrowExpr {
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Call(call)))),
+            ..Default::default()
+        })));
+        CallExpr {
+            span: original.span,
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                obj: Box::new(Expr::Ident(powered_ident_name.into())),
+                prop: MemberProp::Ident("tryRun".into()),
+                ..Default::default()
+            }))),
+            args: vec![thunk(original), thunk(powered)],
+            ..Default::default()
+        }
+    }
+
+    // expect in expect(actual).not.toBe(expected)
+    fn expect_of_matcher_chain<'a>(&self, n: &'a CallExpr) -> Option<&'a Ident> {
+        let head = matcher_chain_head(&n.callee)?;
+        match head.callee.as_expr()?.as_ref() {
+            // expect(...args) is left as is, since its arguments cannot be told from the ones of the matcher
+            Expr::Ident(expect_ident) if self.expect_variables.contains(&expect_ident.to_id()) && !assertion_args(n, true).iter().any(|arg| arg.spread.is_some()) => Some(expect_ident),
+            _ => None
+        }
+    }
+
+    fn create_matcher_chain_run_call(&self, powered_ident_name: &str, original: &CallExpr, args: Vec<ExprOrSpread>) -> CallExpr {
+        // (_paexpect, _paval0, _paval1) => _paexpect(_paval0).not.toBe(_paval1)
+        let param_names: Vec<Atom> = (0..args.len()).map(|idx| format!("_paval{}", idx).into()).collect();
+        let mut chain = original.clone();
+        let mut param_idents = param_names.iter().map(|name| ExprOrSpread::from(Box::new(Expr::Ident(name.clone().into()))));
+        let head = matcher_chain_head_mut(&mut chain.callee).unwrap();
+        head.callee = Callee::Expr(Box::new(Expr::Ident(MATCHER_CHAIN_EXPECT_PARAM_NAME.into())));
+        head.args = param_idents.by_ref().take(head.args.len()).collect();
+        chain.args = param_idents.collect();
+        let params = std::iter::once(Atom::from(MATCHER_CHAIN_EXPECT_PARAM_NAME)).chain(param_names)
+            .map(|name| Pat::Ident(Ident::from(name).into()))
+            .collect();
+        let chain_runner = ExprOrSpread::from(Box::new(Expr::Arrow(ArrowExpr {
+            params,
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Call(chain)))),
+            ..Default::default()
+        })));
+        CallExpr {
+            span: original.span,
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                obj: Box::new(Expr::Ident(powered_ident_name.into())),
+                prop: MemberProp::Ident("runChain".into()),
+                ..Default::default()
+            }))),
+            args: std::iter::once(chain_runner).chain(args).collect(),
+            ..Default::default()
+        }
+    }
+
+    // `vt` of `vt.assert` if `assert` is allowlisted for the module `vt` is imported from
+    fn namespace_of_allowed_member<'a>(&self, obj: &'a Expr, prop: &Atom) -> Option<&'a Ident> {
+        match obj {
+            Expr::Ident(obj_ident) if self.target_namespaces.get(&obj_ident.to_id()).is_some_and(|(_, allow_list)| allow_list.contains(prop)) => Some(obj_ident),
+            _ => None
+        }
+    }
+
+    // name of the assertion function and its receiver if the callee is an assertion
+    fn assertion_callee(&self, callee: &Expr) -> Option<(Atom, Option<Box<Expr>>)> {
+        if is_call_or_apply(callee) {
+            return None;
+        }
+        match callee {
+            // assert(...)
+            Expr::Ident(ident) if self.target_variables.contains_key(&ident.to_id()) => Some((ident.sym.clone(), None)),
+            Expr::Member(MemberExpr { prop: MemberProp::Ident(prop_ident), obj, .. }) => {
+                if let Expr::Ident(obj_ident) = obj.as_ref() {
+                    // assert.equal(...) or vt.assert(...)
+                    if self.target_variables.contains_key(&obj_ident.to_id()) || self.namespace_of_allowed_member(obj, &prop_ident.sym).is_some() {
+                        return Some((prop_ident.sym.clone(), Some(Box::new(Expr::Ident(obj_ident.sym.clone().into())))));
+                    }
+                }
+                // vt.assert.equal(...)
+                match obj.as_ref() {
+                    Expr::Member(MemberExpr { prop: MemberProp::Ident(member_prop_ident), obj: member_obj, .. }) => {
+                        let namespace_ident = self.namespace_of_allowed_member(member_obj, &member_prop_ident.sym)?;
+                        Some((prop_ident.sym.clone(), Some(Box::new(Expr::Member(MemberExpr {
+                            obj: Box::new(Expr::Ident(namespace_ident.sym.clone().into())),
+                            prop: MemberProp::Ident(member_prop_ident.sym.clone().into()),
+                            ..Default::default()
+                        })))))
+                    },
+                    _ => None
+                }
+            },
+            _ => None
+        }
+    }
+
+    // name of the global assertion function and its receiver, if the callee is one of globals or a method of them
+    fn global_assertion_callee(&self, callee: &Expr) -> Option<(Atom, Option<Box<Expr>>)> {
+        if is_call_or_apply(callee) {
+            return None;
+        }
+        let (root, path) = member_path(callee)?;
+        if !self.is_unresolved(root) {
+            return None;
+        }
+        // console.assert(...) for console.assert, or assert.equal(...) for assert
+        let is_target = self.globals.iter().any(|global| path == *global || (path.len() == global.len() + 1 && path.starts_with(global)));
+        if !is_target {
+            return None;
+        }
+        match callee {
+            Expr::Member(MemberExpr { obj, .. }) => Some((path.last().unwrap().clone(), Some(obj.clone()))),
+            _ => Some((root.sym.clone(), None))
+        }
+    }
+
+    // module and name the argument roles of the assertion are looked up by, such as (node:assert, equal) for assert.equal(...).
+    // globals are looked up as members of the module named by their object, such as (console, assert) for console.assert(...)
+    fn callee_origin(&self, callee: &Callee) -> Option<(Atom, Atom)> {
+        let Callee::Expr(callee) = callee else {
+            return None;
+        };
+        match callee.as_ref() {
+            // assert(...)
+            Expr::Ident(ident) if self.target_variables.contains_key(&ident.to_id()) => {
+                let binding = &self.target_variables[&ident.to_id()];
+                return binding.module.clone().map(|module| (module, binding.imported.clone()));
+            },
+            // assert.equal(...), vt.assert(...) or vt.assert.equal(...)
+            Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop_ident), .. }) => {
+                let namespace = match obj.as_ref() {
+                    Expr::Member(MemberExpr { obj: member_obj, .. }) => member_obj,
+                    _ => obj
+                };
+                if let Expr::Ident(namespace_ident) = namespace.as_ref() {
+                    let module = match self.target_variables.get(&namespace_ident.to_id()) {
+                        Some(binding) => Some(binding.module.clone()),
+                        None => self.target_namespaces.get(&namespace_ident.to_id()).map(|(module, _)| Some(module.clone()))
+                    };
+                    if let Some(module) = module {
+                        return module.map(|module| (module, prop_ident.sym.clone()));
+                    }
+                }
+            },
+            _ => {}
+        }
+        let (root, path) = member_path(callee)?;
+        if !self.is_unresolved(root) {
+            return None;
+        }
+        match path.split_last()? {
+            (name, []) => Some((name.clone(), Atom::from("default"))),
+            (name, object) => Some((Atom::from(object.iter().map(Atom::as_str).collect::<Vec<_>>().join(".")), name.clone()))
+        }
+    }
+
+    fn is_console_assert(&self, callee: &Callee) -> bool {
+        let Callee::Expr(callee) = callee else {
+            return false;
+        };
+        member_path(callee).is_some_and(|(root, path)| path == ["console", "assert"] && self.is_unresolved(root))
+    }
+
+    fn is_unresolved(&self, ident: &Ident) -> bool {
+        // identifiers cannot be told from local variables unless the resolver has run
+        self.unresolved_mark.is_some_and(|unresolved_mark| ident.ctxt.outer() == unresolved_mark)
+    }
+
+    // is_matcher_chain is true for expect(actual).not.toBe(expected), whose arguments are the ones of expect and the matcher
+    fn capture_assertion(&mut self, n: &mut CallExpr, prop_ident_name: Atom, receiver: Option<Box<Expr>>, is_matcher_chain: bool) {
+        let (assertion_code, regenerated_pos_map) = match self.extract_assertion_code(n) {
+            Ok(extracted) => extracted,
+            Err(reason) => {
+                // leave the assertion as is
+                self.warn_skipped_assertion(n.span, reason);
+                return;
+            }
+        };
+        // keep the original to restore it when instrumentation fails halfway
+        let original = n.clone();
+        let powered_var_cnt = self.powered_var_cnt;
+        let argrec_var_cnt = self.argrec_var_cnt;
+        let assertion_metadata_vec_len = self.assertion_metadata_vec.len();
+        let argument_metadata_vec_len = self.argument_metadata_vec.len();
+        let assertion_table_len = self.assertion_table.len();
         let mut is_some_arg_captured = false;
         let powered_ident_name = self.next_powered_runner_variable_name();
+        let side_effects = if self.warn_side_effects || self.fast_path {
+            find_side_effects(assertion_args(n, is_matcher_chain))
+        } else {
+            Vec::new()
+        };
+        if self.warn_side_effects {
+            self.warn_side_effects_in_assertion(&side_effects);
+        }
+        // arguments of aliased imports are looked up by the imported name. e.g. strictEqual for eq of `import { strictEqual as eq }`
+        let callee_origin = self.callee_origin(&n.callee);
+        let method_name = callee_origin.as_ref().map_or(&prop_ident_name, |(_, name)| name);
+        // console.assert logs failures instead of throwing, so the instrumented copy would never run on the fast path
+        let is_fast_path = self.fast_path
+            && side_effects.is_empty()
+            && !CALLBACK_ASSERTIONS.contains(&method_name.as_str())
+            && !self.is_console_assert(&n.callee);
         let assertion_start_pos = Utf8Pos(n.span.lo.to_u32());
-
-        let start_pos_usize = (n.span.lo.to_u32() - self.span_offset - 1) as usize;
-        let end_pos_usize = (n.span.hi.to_u32() - self.span_offset - 1) as usize;
-        let assertion_code = self.code[start_pos_usize..end_pos_usize].to_string();
-        let utf16_len = assertion_code.encode_utf16().count();
-        let utf8_len = assertion_code.len();
-        let contains_multibyte_char = utf16_len < utf8_len;
+        let utf16_pos_index = Utf16PosIndex::new(&assertion_code);
 
         self.assertion_metadata = Some(AssertionMetadata {
             ident_name: powered_ident_name.clone(),
             callee_ident_name: prop_ident_name.clone(),
-            receiver_ident_name: obj_ident_name.clone(),
+            receiver,
             assertion_code,
             assertion_start_pos,
-            contains_multibyte_char,
-            binary_op: if n.args.len() == 1 {
-                match n.args.first().unwrap().expr.as_ref() {
+            utf16_pos_index,
+            regenerated_pos_map,
+            skip_reason: None,
+            capture_positions: Vec::new(),
+            table_index: None,
+            capture_count: 0,
+            enclosing_captures: Vec::new(),
+            binexp_operand_spans: Vec::new(),
+            binary_op: if assertion_args(n, is_matcher_chain).len() == 1 {
+                match assertion_args(n, is_matcher_chain).first().unwrap().expr.as_ref() {
                     Expr::Bin(BinExpr{ op, .. }) => {
                         match op.as_str() {
                             "==" | "===" | "!=" | "!==" => Some(op.as_str().into()),
@@ -589,22 +1165,32 @@ impl TransformVisitor {
                 None
             }
         });
+        if let (Some(assertion_metadata), Some(Expr::Bin(BinExpr { left, right, .. }))) = (self.assertion_metadata.as_mut(), assertion_args(n, is_matcher_chain).first().map(|arg| arg.expr.as_ref())) {
+            if assertion_metadata.binary_op.is_some() {
+                assertion_metadata.binexp_operand_spans = vec![left.span(), right.span()];
+            }
+        }
 
         // do not enter assertion callee. e.g. assert in assert(foo)
         // n.callee.visit_mut_children_with(self);
 
+        // indexes after a spread argument do not point to the actual arguments, so instrument all of them
+        let has_spread_arg = assertion_args(n, is_matcher_chain).iter().any(|arg| arg.spread.is_some());
+
         // enter arguments
-        for (idx, arg) in n.args.iter_mut().enumerate() {
-            if let ExprOrSpread { spread: Some(_), .. } = arg {
-                // skip modifying argument if SpreadElement appears immediately beneath assert
-                // assert(...args) looks like one argument syntactically, however there are two or more arguments actually.
-                // power-assert works at the syntax level so it cannot handle SpreadElement that appears immediately beneath assert.
-                return;
+        for (idx, arg) in assertion_args_mut(n, is_matcher_chain).into_iter().enumerate() {
+            // leave messages and error matchers as is. e.g. 'should be ok' in assert.ok(x, 'should be ok')
+            if !has_spread_arg && !self.capture_policy.captures_argument(callee_origin.as_ref(), idx) {
+                continue;
             }
             // const _parg1 = _pasrt1.recorder(0);
             let argrec_ident_name = self.next_argrec_variable_name();
+            let argrec_ident_name_cnt = self.argrec_var_cnt;
             self.argument_metadata = Some(ArgumentMetadata {
                 is_captured: false,
+                // assert(...args) looks like one argument syntactically, however there are two or more arguments actually.
+                // so the spread source is recorded as one argument, and expanded by the runtime.
+                is_spread: arg.spread.is_some(),
                 ident_name: argrec_ident_name.clone(),
                 arg_index: idx,
                 powered_ident_name: powered_ident_name.clone()
@@ -627,95 +1213,894 @@ impl TransformVisitor {
                 if !replaced_with_rec {
                     self.wrap_with_rec_without_pos(arg, &argrec_ident_name);
                 }
+                // _pasrt1.run(_parg1.rec(args, 10, 10, 14)) instead of _pasrt1.run(...args)
+                arg.spread = None;
                 // store argument_metadata to vec for later use
                 self.argument_metadata_vec.push(arg_meta);
                 is_some_arg_captured = true;
-            } else {
-                // unset argrec variable name
+            } else if self.argrec_var_cnt == argrec_ident_name_cnt {
+                // unset argrec variable name unless assertions inside the argument took the following ones
                 // just for compatibility with original power-assert
                 self.unset_argrec_variable_name();
             }
         }
 
-        if is_some_arg_captured {
-            n.callee = self.replace_callee_with_powered_run(&powered_ident_name);
+        // make assertion_metadata None
+        let mut assertion_metadata = self.assertion_metadata.take().unwrap();
+        if let Some(reason) = assertion_metadata.skip_reason {
+            // roll back instrumentation and leave the assertion as is
+            *n = original;
+            self.powered_var_cnt = powered_var_cnt;
+            self.argrec_var_cnt = argrec_var_cnt;
+            self.assertion_metadata_vec.truncate(assertion_metadata_vec_len);
+            self.argument_metadata_vec.truncate(argument_metadata_vec_len);
+            self.assertion_table.truncate(assertion_table_len);
+            self.warn_skipped_assertion(n.span, reason);
+            return;
         }
 
-        // make assertion_metadata None then store it to vec for later use
-        self.assertion_metadata_vec.push(self.assertion_metadata.take().unwrap());
-    }
+        if is_some_arg_captured {
+            if is_matcher_chain {
+                // _pasrt1.runChain((_paexpect, _paval0, _paval1) => _paexpect(_paval0).toBe(_paval1), _parg1.rec(actual, 7, 7, 13), _parg2.rec(expected, 20, 20, 28))
+                let args = assertion_args_mut(n, is_matcher_chain).into_iter().map(|arg| ExprOrSpread { spread: arg.spread, expr: arg.expr.take() }).collect();
+                *n = self.create_matcher_chain_run_call(&powered_ident_name, &original, args);
+            } else {
+                n.callee = self.replace_callee_with_powered_run(&powered_ident_name);
+            }
+            if is_fast_path {
+                // _pasrt1.tryRun(() => assert(truthy), () => _pasrt1.run(_parg1.rec(truthy, 7, 7, 13)))
+                let powered = std::mem::take(n);
+                *n = self.create_fast_path_call(&powered_ident_name, original, powered);
+            }
+        }
 
-}
+        if self.protocol == Protocol::Compact {
+            assertion_metadata.table_index = Some(self.assertion_table.len());
+            self.assertion_table.push(AssertionTableEntry {
+                assertion_code: assertion_metadata.assertion_code.clone(),
+                capture_positions: std::mem::take(&mut assertion_metadata.capture_positions),
+                binary_op: assertion_metadata.binary_op.clone()
+            });
+        }
 
-fn to_utf16_pos(assertion_metadata: &AssertionMetadata, utf8_pos: Utf8Pos) -> Utf16Pos {
-    if !assertion_metadata.contains_multibyte_char {
-        return Utf16Pos(utf8_pos.to_u32())
+        // store assertion_metadata to vec for later use
+        self.assertion_metadata_vec.push(assertion_metadata);
     }
-    let utf8_pos_usize = utf8_pos.to_usize();
-    let assertion_code = &assertion_metadata.assertion_code;
-    let mut iter = assertion_code.chars();
-    let mut current_utf16_pos = 0;
-    let mut current_utf8_pos = 0;
-    while current_utf8_pos < utf8_pos_usize {
-        let c = iter.next().unwrap();
-        current_utf8_pos += c.len_utf8();
-        current_utf16_pos += c.len_utf16();
+
+    // scans top-level imports and requires only, to tell whether the program may contain assertions
+    fn references_target_module(&self, program: &Program) -> bool {
+        match program {
+            Program::Module(module) => module.body.iter().any(|item| {
+                match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { src, .. })) => self.target_module_allow_list(&src.value).is_some() || self.is_expect_module(&src.value),
+                    ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import_equals_decl)) => {
+                        match import_equals_decl.as_ref() {
+                            TsImportEqualsDecl { module_ref: TsModuleRef::TsExternalModuleRef(external_module_ref), .. } => self.target_module_allow_list(&external_module_ref.expr.value).is_some(),
+                            _ => false
+                        }
+                    },
+                    ModuleItem::Stmt(stmt) => self.requires_target_module(stmt),
+                    _ => false
+                }
+            }),
+            Program::Script(script) => script.body.iter().any(|stmt| self.requires_target_module(stmt))
+        }
     }
-    Utf16Pos(current_utf16_pos as u32)
-}
 
-impl VisitMut for TransformVisitor {
-    // Implement necessary visit_mut_* methods for actual custom transform.
-    // A comprehensive list of possible visitor methods can be found here:
-    // https://rustdoc.swc.rs/swc_ecma_visit/trait.VisitMut.html
+    // allowlist of the module, matching globs when the module is not listed as is
+    fn target_module_allow_list(&self, source: &Wtf8Atom) -> Option<&FxHashSet<Atom>> {
+        if let Some(allow_list) = self.target_modules.get(source) {
+            return Some(allow_list);
+        }
+        let source = source.as_str()?;
+        self.target_module_patterns.iter()
+            .find(|(pattern, _)| pattern.is_match(source))
+            .map(|(_, allow_list)| allow_list)
+    }
 
-    fn visit_mut_program(&mut self, n: &mut Program) {
-        // store span as offset at the start of Program node due to SWC issue https://github.com/swc-project/swc/issues/1366
-        self.span_offset = n.span_lo().to_u32() - 1;
-        n.visit_mut_children_with(self);
+    // registers functions such as assertUser imported or declared anywhere in the program, since declarations are hoisted,
+    // and tells whether the program references globals, in a single traversal skipped when neither is to be found
+    fn prescan(&mut self, program: &Program, finds_globals: bool) -> bool {
+        let global_roots: FxHashSet<Atom> = if finds_globals && self.unresolved_mark.is_some() {
+            self.globals.iter().map(|global| global[0].clone()).collect()
+        } else {
+            FxHashSet::default()
+        };
+        if self.callee_patterns.is_empty() && global_roots.is_empty() {
+            return false;
+        }
+        let mut scanner = PreScanner {
+            visitor: self,
+            global_roots,
+            references_globals: false,
+            callee_pattern_bindings: Vec::new()
+        };
+        program.visit_with(&mut scanner);
+        let PreScanner { references_globals, callee_pattern_bindings, .. } = scanner;
+        self.target_variables.extend(callee_pattern_bindings);
+        references_globals
     }
 
-    fn visit_mut_module(&mut self, n: &mut Module) {
-        // store span as offset at the start of Module node due to SWC issue https://github.com/swc-project/swc/issues/1366
-        self.span_offset = n.span_lo().to_u32() - 1;
-        n.visit_mut_children_with(self);
+    fn is_target_of_in_source_testing(&self) -> bool {
+        self.target_modules.contains_key(&Atom::from(VITEST_MODULE)) || self.expect_modules.contains(&Atom::from(VITEST_MODULE))
     }
 
-    fn visit_mut_import_decl(&mut self, n: &mut ImportDecl) {
-        if self.target_modules.contains_key(&n.src.value) {
+    // const { it, assert } = import.meta.vitest; defines assert as if it were imported from vitest
+    fn register_in_source_testing_variables(&mut self, name: &Pat) {
+        match name {
+            Pat::Object(ObjectPat { props, .. }) => {
+                for prop in props.iter() {
+                    let (imported, local) = match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key: PropName::Ident(key), value, .. }) => match value.as_ref() {
+                            Pat::Ident(BindingIdent { id, .. }) => (&key.sym, id),
+                            _ => continue
+                        },
+                        ObjectPatProp::Assign(AssignPatProp { key, value: None, .. }) => (&key.id.sym, &key.id),
+                        _ => continue
+                    };
+                    if self.target_modules.get(&Atom::from(VITEST_MODULE)).is_some_and(|allow_list| allow_list.is_empty() || allow_list.contains(imported)) {
+                        self.target_variables.insert(local.to_id(), TargetBinding { module: Some(Atom::from(VITEST_MODULE)), imported: imported.clone() });
+                    } else if imported == "expect" && self.expect_modules.contains(&Atom::from(VITEST_MODULE)) {
+                        self.expect_variables.insert(local.to_id());
+                    }
+                }
+            },
+            // const vt = import.meta.vitest; vt.assert(...)
+            Pat::Ident(BindingIdent { id, .. }) => match self.target_modules.get(&Atom::from(VITEST_MODULE)) {
+                Some(allow_list) if allow_list.is_empty() => {
+                    self.target_variables.insert(id.to_id(), TargetBinding { module: Some(Atom::from(VITEST_MODULE)), imported: Atom::from("*") });
+                },
+                Some(allow_list) => {
+                    self.target_namespaces.insert(id.to_id(), (Atom::from(VITEST_MODULE), allow_list.clone()));
+                },
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn is_expect_module(&self, src: &Wtf8Atom) -> bool {
+        self.expect_modules.contains(src)
+    }
+
+    fn requires_target_module(&self, stmt: &Stmt) -> bool {
+        match stmt {
+            // if (import.meta.vitest) { const { it, assert } = import.meta.vitest; }
+            Stmt::If(IfStmt { test, .. }) => is_import_meta_vitest(test) && self.is_target_of_in_source_testing(),
+            // const assert = require('node:assert');
+            Stmt::Decl(Decl::Var(var_decl)) => var_decl.decls.iter().any(|declarator| {
+                declarator.init.as_ref().is_some_and(|init| self.is_require_of_target_module(init))
+            }),
+            Stmt::Expr(ExprStmt { expr, .. }) => self.is_require_of_target_module(expr),
+            _ => false
+        }
+    }
+
+    fn is_require_of_target_module(&self, expr: &Expr) -> bool {
+        match expr {
+            // require('node:assert').strict
+            Expr::Member(MemberExpr { obj, .. }) => self.is_require_of_target_module(obj),
+            Expr::Call(CallExpr { callee: Callee::Expr(callee), args, .. }) => {
+                match (callee.as_ref(), args.as_slice()) {
+                    (Expr::Ident(Ident { sym, .. }), [ExprOrSpread { spread: None, expr: arg }]) if sym == "require" => {
+                        matches!(arg.as_ref(), Expr::Lit(Lit::Str(Str { value, .. })) if self.target_module_allow_list(value).is_some())
+                    },
+                    _ => false
+                }
+            },
+            _ => false
+        }
+    }
+
+    fn extract_assertion_code(&self, n: &CallExpr) -> Result<(String, Option<FxHashMap<BytePos, Utf8Pos>>), SkipReason> {
+        if let Some(source_map) = &self.source_map {
+            // resolve through the source map since spans may come from more than one source file
+            if let Ok(assertion_code) = source_map.span_to_snippet(n.span) {
+                return Ok((assertion_code, None));
+            }
+        }
+        // original source is not available, so regenerate assertion code from the AST
+        let RegeneratedCode { code, pos_map } = regenerate_assertion_code(n).ok_or(SkipReason::CodeNotRegenerated)?;
+        Ok((code, Some(pos_map)))
+    }
+
+    fn warn_skipped_assertion(&self, span: Span, reason: SkipReason) {
+        if !HANDLER.is_set() {
+            return;
+        }
+        let message = match &self.filename {
+            Some(filename) => format!("power-assert: assertion in {} is left uninstrumented because {}", filename, reason),
+            None => format!("power-assert: assertion is left uninstrumented because {}", reason)
+        };
+        HANDLER.with(|handler| {
+            handler.struct_span_warn(span, &message).emit();
+        });
+    }
+
+    fn warn_side_effects_in_assertion(&self, side_effects: &[(Span, SideEffect)]) {
+        if !HANDLER.is_set() {
+            return;
+        }
+        for (span, side_effect) in side_effects.iter().filter(|(_, side_effect)| side_effect.is_mutation()) {
+            let message = match &self.filename {
+                Some(filename) => format!("power-assert: {} in {} changes state, so the assertion behaves differently when stripped or re-run", side_effect, filename),
+                None => format!("power-assert: {} changes state, so the assertion behaves differently when stripped or re-run", side_effect)
+            };
+            HANDLER.with(|handler| {
+                handler.struct_span_warn(*span, &message).emit();
+            });
+        }
+    }
+}
+
+#[derive(Default)]
+struct SpanCollector {
+    spans: Vec<Span>
+}
+
+impl Visit for SpanCollector {
+    fn visit_span(&mut self, n: &Span) {
+        self.spans.push(*n);
+    }
+}
+
+// methods of Array, Map and Set that mutate the receiver
+// assertions calling back their argument, which the instrumented copy on the fast path would call once more
+const CALLBACK_ASSERTIONS: [&str; 6] = ["throws", "doesNotThrow", "rejects", "doesNotReject", "assertThrows", "assertRejects"];
+
+const MUTATING_METHODS: [&str; 13] = ["push", "pop", "shift", "unshift", "splice", "sort", "reverse", "fill", "copyWithin", "set", "add", "delete", "clear"];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum SideEffect {
+    Assignment,
+    Update,
+    Delete,
+    MutatingMethodCall(Atom),
+    // may have side effects, though not known to change state
+    Call,
+    New,
+    Await,
+    Yield,
+    TaggedTemplate,
+    Jsx,
+    Spread,
+    // body is not evaluated with the arguments, though assertions like `assert.throws` call it
+    Function
+}
+
+impl SideEffect {
+    fn is_mutation(&self) -> bool {
+        matches!(self, SideEffect::Assignment | SideEffect::Update | SideEffect::Delete | SideEffect::MutatingMethodCall(_))
+    }
+}
+
+impl fmt::Display for SideEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SideEffect::Assignment => write!(f, "assignment"),
+            SideEffect::Update => write!(f, "increment or decrement"),
+            SideEffect::Delete => write!(f, "`delete`"),
+            SideEffect::MutatingMethodCall(method) => write!(f, "call to mutating method `{}`", method),
+            SideEffect::Call => write!(f, "call"),
+            SideEffect::New => write!(f, "`new`"),
+            SideEffect::Await => write!(f, "`await`"),
+            SideEffect::Yield => write!(f, "`yield`"),
+            SideEffect::TaggedTemplate => write!(f, "tagged template"),
+            SideEffect::Jsx => write!(f, "JSX"),
+            SideEffect::Spread => write!(f, "spread"),
+            SideEffect::Function => write!(f, "function")
+        }
+    }
+}
+
+
+// warns about assertions in scripts, such as console.assert(x) of globals
+struct ScriptAssertionReporter<'a> {
+    visitor: &'a TransformVisitor
+}
+
+impl Visit for ScriptAssertionReporter<'_> {
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        let is_assertion = match &n.callee {
+            Callee::Expr(expr) => self.visitor.assertion_callee(expr).or_else(|| self.visitor.global_assertion_callee(expr)).is_some(),
+            _ => false
+        } || self.visitor.expect_of_matcher_chain(n).is_some();
+        if is_assertion {
+            self.visitor.warn_skipped_assertion(n.span, SkipReason::Script);
+        }
+        n.visit_children_with(self);
+    }
+}
+
+// finds bindings of functions whose names match the callee patterns, such as assertUser,
+// and unresolved references to the root of globals, such as console of console.assert
+struct PreScanner<'a> {
+    visitor: &'a TransformVisitor,
+    global_roots: FxHashSet<Atom>,
+    references_globals: bool,
+    callee_pattern_bindings: Vec<(Id, TargetBinding)>
+}
+
+impl PreScanner<'_> {
+    fn check(&mut self, ident: &Ident, binding: impl FnOnce() -> TargetBinding) {
+        if self.visitor.callee_patterns.iter().any(|pattern| pattern.is_match(&ident.sym)) {
+            self.callee_pattern_bindings.push((ident.to_id(), binding()));
+        }
+    }
+}
+
+impl Visit for PreScanner<'_> {
+    // the rest of the program is skipped once a global is found, unless bindings of callee patterns are still to be found
+    fn visit_stmt(&mut self, n: &Stmt) {
+        if self.references_globals && self.visitor.callee_patterns.is_empty() {
+            return;
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_ident(&mut self, n: &Ident) {
+        if self.global_roots.contains(&n.sym) && self.visitor.is_unresolved(n) {
+            self.references_globals = true;
+        }
+    }
+
+    fn visit_import_decl(&mut self, n: &ImportDecl) {
+        for specifier in n.specifiers.iter() {
+            match specifier {
+                ImportSpecifier::Named(ImportNamedSpecifier { local, imported, .. }) => self.check(local, || TargetBinding::imported_from(&n.src, imported_name(imported.as_ref(), local))),
+                ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => self.check(local, || TargetBinding::imported_from(&n.src, Atom::from("default"))),
+                // namespace imports are not functions
+                ImportSpecifier::Namespace(_) => {}
+            }
+        }
+    }
+
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        self.check(&n.ident, || TargetBinding { module: None, imported: n.ident.sym.clone() });
+        n.visit_children_with(self);
+    }
+
+    // const assertUser = (u) => { ... }; but not const assertResult = compute();
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let (Pat::Ident(BindingIdent { id, .. }), Some(init)) = (&n.name, n.init.as_deref()) {
+            if is_function_or_require(init) {
+                self.check(id, || TargetBinding { module: None, imported: id.sym.clone() });
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+// initializers of functions, including the ones required as in require('./helpers.js').assertUser
+fn is_function_or_require(init: &Expr) -> bool {
+    match init {
+        Expr::Fn(_) | Expr::Arrow(_) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_function_or_require(expr),
+        Expr::Member(MemberExpr { obj, .. }) => is_require_call(obj),
+        _ => is_require_call(init)
+    }
+}
+
+fn is_require_call(expr: &Expr) -> bool {
+    matches!(expr, Expr::Call(CallExpr { callee: Callee::Expr(callee), .. }) if matches!(callee.as_ref(), Expr::Ident(Ident { sym, .. }) if sym == "require"))
+}
+
+// strictEqual of `import { strictEqual as eq }`
+fn imported_name(imported: Option<&ModuleExportName>, local: &Ident) -> Atom {
+    match imported {
+        Some(ModuleExportName::Ident(imported_ident)) => imported_ident.sym.clone(),
+        Some(ModuleExportName::Str(imported_str)) => imported_str.value.as_str().map_or_else(|| local.sym.clone(), Atom::from),
+        None => local.sym.clone()
+    }
+}
+
+// module specifiers containing glob characters
+fn is_glob(source: &str) -> bool {
+    source.contains(['*', '?'])
+}
+
+// ** matches any number of path segments including . and .., * and ? do not match /
+fn glob_to_regex(glob: &str) -> Regex {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:[^/]*/)*");
+                } else {
+                    pattern.push_str(".*");
+                }
+            },
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            _ => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4])))
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).expect("escaped glob should be a valid regex")
+}
+
+// [console, assert] and console of console.assert
+// console.assert.call(console, x) calls the assertion rather than its method, with arguments shifted by the receiver, so it is left as is
+fn is_call_or_apply(callee: &Expr) -> bool {
+    matches!(callee, Expr::Member(MemberExpr { prop: MemberProp::Ident(prop_ident), .. }) if prop_ident.sym == "call" || prop_ident.sym == "apply")
+}
+
+fn member_path(expr: &Expr) -> Option<(&Ident, Vec<Atom>)> {
+    match expr {
+        Expr::Ident(ident) => Some((ident, vec![ident.sym.clone()])),
+        Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop), .. }) => {
+            let (root, mut path) = member_path(obj)?;
+            path.push(prop.sym.clone());
+            Some((root, path))
+        },
+        _ => None
+    }
+}
+
+// finds nodes that may have side effects when evaluated, or evaluated twice
+#[derive(Default)]
+struct SideEffectFinder {
+    side_effects: Vec<(Span, SideEffect)>
+}
+
+impl Visit for SideEffectFinder {
+    fn visit_expr(&mut self, n: &Expr) {
+        let side_effect = match n {
+            Expr::Call(CallExpr { callee: Callee::Expr(callee), .. }) => match callee.as_ref() {
+                Expr::Member(MemberExpr { prop: MemberProp::Ident(prop), .. }) if MUTATING_METHODS.contains(&prop.sym.as_str()) => Some(SideEffect::MutatingMethodCall(prop.sym.clone())),
+                _ => Some(SideEffect::Call)
+            },
+            Expr::Call(_) => Some(SideEffect::Call),
+            Expr::OptChain(OptChainExpr { base, .. }) if matches!(base.as_ref(), OptChainBase::Call(_)) => Some(SideEffect::Call),
+            Expr::New(_) => Some(SideEffect::New),
+            Expr::Assign(_) => Some(SideEffect::Assignment),
+            Expr::Update(_) => Some(SideEffect::Update),
+            Expr::Unary(UnaryExpr { op: UnaryOp::Delete, .. }) => Some(SideEffect::Delete),
+            Expr::Await(_) => Some(SideEffect::Await),
+            Expr::Yield(_) => Some(SideEffect::Yield),
+            Expr::TaggedTpl(_) => Some(SideEffect::TaggedTemplate),
+            Expr::JSXElement(_) | Expr::JSXFragment(_) => Some(SideEffect::Jsx),
+            Expr::Fn(_) | Expr::Arrow(_) | Expr::Class(_) => {
+                self.side_effects.push((n.span(), SideEffect::Function));
+                return;
+            },
+            _ => None
+        };
+        if let Some(side_effect) = side_effect {
+            self.side_effects.push((n.span(), side_effect));
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_expr_or_spread(&mut self, n: &ExprOrSpread) {
+        // spreading runs the iterator protocol
+        if let Some(spread_span) = n.spread {
+            self.side_effects.push((spread_span.with_hi(n.expr.span_hi()), SideEffect::Spread));
+        }
+        n.visit_children_with(self);
+    }
+}
+
+// arguments without side effects evaluate to the same values when the assertion is re-run
+fn find_side_effects(args: Vec<&ExprOrSpread>) -> Vec<(Span, SideEffect)> {
+    let mut finder = SideEffectFinder::default();
+    for arg in args {
+        arg.visit_with(&mut finder);
+    }
+    finder.side_effects
+}
+
+fn is_import_meta_vitest(expr: &Expr) -> bool {
+    match expr {
+        Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop), .. }) => {
+            prop.sym == "vitest" && matches!(obj.as_ref(), Expr::MetaProp(MetaPropExpr { kind: MetaPropKind::ImportMeta, .. }))
+        },
+        _ => false
+    }
+}
+
+// expect(actual) of expect(actual).not.toBe(expected)
+fn matcher_chain_head(callee: &Callee) -> Option<&CallExpr> {
+    fn head_of(expr: &Expr) -> Option<&CallExpr> {
+        let Expr::Member(MemberExpr { obj, .. }) = expr else {
+            return None;
+        };
+        match obj.as_ref() {
+            Expr::Call(head) => Some(head),
+            obj => head_of(obj)
+        }
+    }
+    head_of(callee.as_expr()?)
+}
+
+fn matcher_chain_head_mut(callee: &mut Callee) -> Option<&mut CallExpr> {
+    fn head_of(expr: &mut Expr) -> Option<&mut CallExpr> {
+        let Expr::Member(MemberExpr { obj, .. }) = expr else {
+            return None;
+        };
+        match obj.as_mut() {
+            Expr::Call(head) => Some(head),
+            obj => head_of(obj)
+        }
+    }
+    head_of(callee.as_mut_expr()?)
+}
+
+// arguments of the assertion, which are the ones of expect and the matcher for matcher chains
+fn assertion_args(n: &CallExpr, is_matcher_chain: bool) -> Vec<&ExprOrSpread> {
+    match matcher_chain_head(&n.callee).filter(|_| is_matcher_chain) {
+        Some(head) => head.args.iter().chain(n.args.iter()).collect(),
+        None => n.args.iter().collect()
+    }
+}
+
+fn assertion_args_mut(n: &mut CallExpr, is_matcher_chain: bool) -> Vec<&mut ExprOrSpread> {
+    let CallExpr { callee, args, .. } = n;
+    match matcher_chain_head_mut(callee).filter(|_| is_matcher_chain) {
+        Some(head) => head.args.iter_mut().chain(args.iter_mut()).collect(),
+        None => args.iter_mut().collect()
+    }
+}
+
+fn collect_spans<N: VisitWith<SpanCollector>>(n: &N) -> Vec<Span> {
+    let mut collector = SpanCollector::default();
+    n.visit_with(&mut collector);
+    collector.spans
+}
+
+fn regenerate_assertion_code(n: &CallExpr) -> Option<RegeneratedCode> {
+    let mut buf = Vec::new();
+    {
+        let cm: Lrc<SourceMap> = Default::default();
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments: None,
+            wr: JsWriter::new(cm, "\n", &mut buf, None)
+        };
+        n.emit_with(&mut emitter).ok()?;
+    }
+    let code = String::from_utf8(buf).ok()?;
+
+    // parse regenerated code again to know where each node is placed in it
+    let start_pos = BytePos(1);
+    let end_pos = start_pos + BytePos(code.len() as u32);
+    let reparsed = [
+        Syntax::Es(EsSyntax { jsx: true, ..Default::default() }),
+        Syntax::Typescript(Default::default())
+    ].into_iter().find_map(|syntax| {
+        let lexer = Lexer::new(syntax, EsVersion::latest(), StringInput::new(&code, start_pos, end_pos), None);
+        let mut parser = Parser::new_from(lexer);
+        let expr = parser.parse_expr().ok()?;
+        parser.take_errors().is_empty().then_some(expr)
+    })?;
+
+    // both trees have the same shape, so their spans correspond one-to-one in visiting order
+    let original_spans = collect_spans(n);
+    let regenerated_spans = collect_spans(&reparsed);
+    if original_spans.len() != regenerated_spans.len() {
+        return None;
+    }
+    let mut pos_map = FxHashMap::default();
+    for (original, regenerated) in original_spans.iter().zip(regenerated_spans.iter()) {
+        pos_map.entry(original.lo).or_insert(Utf8Pos(regenerated.lo.to_u32() - start_pos.to_u32()));
+        pos_map.entry(original.hi).or_insert(Utf8Pos(regenerated.hi.to_u32() - start_pos.to_u32()));
+    }
+    Some(RegeneratedCode {
+        code,
+        pos_map
+    })
+}
+
+// ESTree node types captured by default, which are `caputuringTargetTypes` of transpiler-core
+// plus the ones swc captures in addition. SequenceExpression and ParenthesizedExpression are
+// never captured, and FunctionExpression is not entered at all.
+const CAPTURED_NODE_TYPES: [&str; 33] = [
+    // caputuringTargetTypes of transpiler-core
+    "Literal",
+    "ObjectExpression",
+    "ArrayExpression",
+    "ConditionalExpression",
+    "Identifier",
+    "MemberExpression",
+    "CallExpression",
+    "UnaryExpression",
+    "BinaryExpression",
+    "LogicalExpression",
+    "AssignmentExpression",
+    "NewExpression",
+    "UpdateExpression",
+    "YieldExpression",
+    "AwaitExpression",
+    "TemplateLiteral",
+    "TaggedTemplateExpression",
+    // captured by swc only
+    "ThisExpression",
+    "ArrowFunctionExpression",
+    "ClassExpression",
+    "MetaProperty",
+    "PrivateIdentifier",
+    "JSXMemberExpression",
+    "JSXNamespacedName",
+    "JSXEmptyExpression",
+    "JSXElement",
+    "JSXFragment",
+    "TSTypeAssertion",
+    "TSAsExpression",
+    "TSNonNullExpression",
+    "TSInstantiationExpression",
+    "TSSatisfiesExpression",
+    "Invalid"
+];
+
+// indexes of instrumented arguments per function of a module
+type ArgumentRoles = &'static [(&'static str, &'static [usize])];
+
+// indexes of instrumented arguments of node:assert functions, where `default` is the default export.
+// messages, error matchers and deltas are left as is. functions not in the table have every argument instrumented.
+const NODE_ASSERT_ARGUMENTS: [(&str, &[usize]); 20] = [
+    ("default", &[0]),
+    ("strict", &[0]),
+    ("ok", &[0]),
+    ("equal", &[0, 1]),
+    ("notEqual", &[0, 1]),
+    ("strictEqual", &[0, 1]),
+    ("notStrictEqual", &[0, 1]),
+    ("deepEqual", &[0, 1]),
+    ("notDeepEqual", &[0, 1]),
+    ("deepStrictEqual", &[0, 1]),
+    ("notDeepStrictEqual", &[0, 1]),
+    ("partialDeepStrictEqual", &[0, 1]),
+    ("throws", &[0]),
+    ("doesNotThrow", &[0]),
+    ("rejects", &[0]),
+    ("doesNotReject", &[0]),
+    ("match", &[0, 1]),
+    ("doesNotMatch", &[0, 1]),
+    ("ifError", &[0]),
+    ("fail", &[])
+];
+
+// indexes of instrumented arguments of chai assert, named `assert` for `assert(expr, message)` and also exported by vitest
+const CHAI_ASSERT_ARGUMENTS: [(&str, &[usize]); 53] = [
+    ("assert", &[0]),
+    ("ok", &[0]),
+    ("equal", &[0, 1]),
+    ("notEqual", &[0, 1]),
+    ("strictEqual", &[0, 1]),
+    ("notStrictEqual", &[0, 1]),
+    ("deepEqual", &[0, 1]),
+    ("notDeepEqual", &[0, 1]),
+    ("deepStrictEqual", &[0, 1]),
+    ("throws", &[0]),
+    ("doesNotThrow", &[0]),
+    ("match", &[0, 1]),
+    ("notMatch", &[0, 1]),
+    ("fail", &[]),
+    ("isOk", &[0]),
+    ("isNotOk", &[0]),
+    ("isTrue", &[0]),
+    ("isNotTrue", &[0]),
+    ("isFalse", &[0]),
+    ("isNotFalse", &[0]),
+    ("isNull", &[0]),
+    ("isNotNull", &[0]),
+    ("isUndefined", &[0]),
+    ("isDefined", &[0]),
+    ("isNaN", &[0]),
+    ("isNotNaN", &[0]),
+    ("exists", &[0]),
+    ("notExists", &[0]),
+    ("isEmpty", &[0]),
+    ("isNotEmpty", &[0]),
+    ("isFunction", &[0]),
+    ("isObject", &[0]),
+    ("isArray", &[0]),
+    ("isString", &[0]),
+    ("isNumber", &[0]),
+    ("isBoolean", &[0]),
+    ("typeOf", &[0]),
+    ("instanceOf", &[0]),
+    ("notInstanceOf", &[0]),
+    ("include", &[0, 1]),
+    ("notInclude", &[0, 1]),
+    ("deepInclude", &[0, 1]),
+    ("property", &[0]),
+    ("lengthOf", &[0, 1]),
+    ("closeTo", &[0, 1]),
+    ("approximately", &[0, 1]),
+    ("sameMembers", &[0, 1]),
+    ("sameDeepMembers", &[0, 1]),
+    ("includeMembers", &[0, 1]),
+    ("isAbove", &[0, 1]),
+    ("isBelow", &[0, 1]),
+    ("isAtLeast", &[0, 1]),
+    ("isAtMost", &[0, 1])
+];
+
+// modules with built-in argument roles, in addition to the ones of presets
+const BUILTIN_CAPTURED_ARGUMENTS: [(&str, ArgumentRoles); 6] = [
+    ("node:assert", &NODE_ASSERT_ARGUMENTS),
+    ("node:assert/strict", &NODE_ASSERT_ARGUMENTS),
+    ("assert", &NODE_ASSERT_ARGUMENTS),
+    ("assert/strict", &NODE_ASSERT_ARGUMENTS),
+    ("vitest", &CHAI_ASSERT_ARGUMENTS),
+    // console.assert of globals
+    ("console", &[("assert", &[0])])
+];
+
+struct CapturePolicy {
+    node_types: FxHashSet<&'static str>,
+    // by module and name
+    arguments: FxHashMap<(Atom, Atom), Vec<usize>>,
+    // overrides for modules given as globs, such as **/test-utils/*.js
+    argument_patterns: Vec<(Regex, FxHashMap<Atom, Vec<usize>>)>
+}
+
+impl CapturePolicy {
+    fn new(config: &Config) -> Self {
+        let mut node_types: FxHashSet<&'static str> = CAPTURED_NODE_TYPES.into_iter().collect();
+        if !config.capture_literals {
+            node_types.remove("Literal");
+        }
+        if !config.capture_this {
+            node_types.remove("ThisExpression");
+        }
+        // config overrides presets, which override the built-in table
+        let builtin_arguments = BUILTIN_CAPTURED_ARGUMENTS.into_iter().map(|(module, arguments)| (Atom::from(module), arguments));
+        let preset_arguments = config.presets.iter().flat_map(|preset| {
+            preset.modules().into_iter().map(|module| (Atom::from(module.source()), preset.captured_arguments()))
+        });
+        let mut arguments: FxHashMap<(Atom, Atom), Vec<usize>> = FxHashMap::default();
+        for (module, module_arguments) in builtin_arguments.chain(preset_arguments) {
+            arguments.extend(module_arguments.iter().map(|(name, indexes)| ((module.clone(), Atom::from(*name)), indexes.to_vec())));
+        }
+        let mut argument_patterns = Vec::new();
+        for (module, module_arguments) in config.captured_arguments.iter() {
+            let module_arguments = module_arguments.iter().map(|(name, indexes)| (Atom::from(name.as_str()), indexes.clone()));
+            if is_glob(module) {
+                argument_patterns.push((glob_to_regex(module), module_arguments.collect()));
+            } else {
+                arguments.extend(module_arguments.map(|(name, indexes)| ((Atom::from(module.as_str()), name), indexes)));
+            }
+        }
+        CapturePolicy { node_types, arguments, argument_patterns }
+    }
+
+    // functions declared in the program and matcher chains have no origin, so every argument of them is captured
+    fn captures_argument(&self, origin: Option<&(Atom, Atom)>, idx: usize) -> bool {
+        let Some((module, name)) = origin else {
+            return true;
+        };
+        self.argument_patterns.iter()
+            .find(|(pattern, _)| pattern.is_match(module))
+            .and_then(|(_, arguments)| arguments.get(name))
+            .or_else(|| self.arguments.get(&(module.clone(), name.clone())))
+            .is_none_or(|indexes| indexes.contains(&idx))
+    }
+
+    fn captures(&self, expr: &Expr) -> bool {
+        self.node_types.contains(estree_node_type(expr))
+    }
+}
+
+// node type of ESTree, which the runtime and transpiler-core are built on
+fn estree_node_type(expr: &Expr) -> &'static str {
+    match expr {
+        Expr::This(_) => "ThisExpression",
+        Expr::Array(_) => "ArrayExpression",
+        Expr::Object(_) => "ObjectExpression",
+        Expr::Fn(_) => "FunctionExpression",
+        Expr::Unary(_) => "UnaryExpression",
+        Expr::Update(_) => "UpdateExpression",
+        Expr::Bin(BinExpr { op, .. }) => match op.as_str() {
+            "||" | "&&" | "??" => "LogicalExpression",
+            _ => "BinaryExpression"
+        },
+        Expr::Assign(_) => "AssignmentExpression",
+        Expr::Member(_) | Expr::SuperProp(_) => "MemberExpression",
+        Expr::Cond(_) => "ConditionalExpression",
+        Expr::Call(_) => "CallExpression",
+        Expr::New(_) => "NewExpression",
+        Expr::Seq(_) => "SequenceExpression",
+        Expr::Ident(_) => "Identifier",
+        Expr::Lit(_) => "Literal",
+        Expr::Tpl(_) => "TemplateLiteral",
+        Expr::TaggedTpl(_) => "TaggedTemplateExpression",
+        Expr::Arrow(_) => "ArrowFunctionExpression",
+        Expr::Class(_) => "ClassExpression",
+        Expr::Yield(_) => "YieldExpression",
+        Expr::MetaProp(_) => "MetaProperty",
+        Expr::Await(_) => "AwaitExpression",
+        Expr::Paren(_) => "ParenthesizedExpression",
+        Expr::JSXMember(_) => "JSXMemberExpression",
+        Expr::JSXNamespacedName(_) => "JSXNamespacedName",
+        Expr::JSXEmpty(_) => "JSXEmptyExpression",
+        Expr::JSXElement(_) => "JSXElement",
+        Expr::JSXFragment(_) => "JSXFragment",
+        Expr::TsTypeAssertion(_) => "TSTypeAssertion",
+        Expr::TsConstAssertion(_) | Expr::TsAs(_) => "TSAsExpression",
+        Expr::TsNonNull(_) => "TSNonNullExpression",
+        Expr::TsInstantiation(_) => "TSInstantiationExpression",
+        Expr::TsSatisfies(_) => "TSSatisfiesExpression",
+        Expr::PrivateName(_) => "PrivateIdentifier",
+        // ESTree wraps the optional member or call in ChainExpression, the wrapped node is what is captured
+        Expr::OptChain(OptChainExpr { base, .. }) => match base.as_ref() {
+            OptChainBase::Member(_) => "MemberExpression",
+            OptChainBase::Call(_) => "CallExpression"
+        },
+        Expr::Invalid(_) => "Invalid"
+    }
+}
+
+fn to_utf16_pos(assertion_metadata: &AssertionMetadata, utf8_pos: Utf8Pos) -> Result<Utf16Pos, SkipReason> {
+    if !assertion_metadata.assertion_code.is_char_boundary(utf8_pos.to_usize()) {
+        // position in the middle of a multibyte char cannot be mapped
+        return Err(SkipReason::SpanOutOfAssertion);
+    }
+    Ok(assertion_metadata.utf16_pos_index.lookup(utf8_pos))
+}
+
+impl VisitMut for TransformVisitor {
+    // Implement necessary visit_mut_* methods for actual custom transform.
+    // A comprehensive list of possible visitor methods can be found here:
+    // https://rustdoc.swc.rs/swc_ecma_visit/trait.VisitMut.html
+
+    fn visit_mut_program(&mut self, n: &mut Program) {
+        // most of the files going through the pipeline are not tests, so return them without deep traversal,
+        // or with a read-only one when globals or callee patterns are configured
+        let references_target_module = self.references_target_module(n);
+        let references_globals = self.prescan(n, !references_target_module);
+        if !references_target_module && !references_globals && self.target_variables.is_empty() {
+            return;
+        }
+        // instrumented scripts would call _power_ without importing it, so they are reported and left as is
+        if let Program::Script(script) = n {
+            script.visit_with(&mut ScriptAssertionReporter { visitor: self });
+            return;
+        }
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_import_decl(&mut self, n: &mut ImportDecl) {
+        if self.is_expect_module(&n.src.value) {
+            for s in &n.specifiers {
+                if let ImportSpecifier::Named(ImportNamedSpecifier { local, imported, .. }) = s {
+                    let is_expect = match imported {
+                        Some(ModuleExportName::Ident(imported_ident)) => imported_ident.sym == "expect",
+                        Some(ModuleExportName::Str(imported_str)) => imported_str.value == "expect",
+                        None => local.sym == "expect"
+                    };
+                    if is_expect {
+                        self.expect_variables.insert(local.to_id());
+                    }
+                }
+            }
+        }
+        if let Some(allow_list) = self.target_module_allow_list(&n.src.value).cloned() {
             for s in &mut n.specifiers {
+                let default_or_namespace = if matches!(s, ImportSpecifier::Default(_)) { "default" } else { "*" };
                 match s {
-                    ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
-                        self.target_variables.insert(local.to_id());
-                    },
-                    ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
-                        self.target_variables.insert(local.to_id());
+                    ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                        if allow_list.is_empty() {
+                            self.target_variables.insert(local.to_id(), TargetBinding::imported_from(&n.src, Atom::from(default_or_namespace)));
+                        } else {
+                            // import * as vt from 'vitest'; only vt.assert is a target
+                            self.target_namespaces.insert(local.to_id(), (n.src.value.to_atom_lossy().into_owned(), allow_list.clone()));
+                        }
                     },
                     ImportSpecifier::Named(ImportNamedSpecifier { local, imported, .. }) => {
-                        let module_name = &n.src.value;
-                        match imported {
-                            Some(imported_name) => {
-                                match imported_name {
-                                    ModuleExportName::Ident(imported_ident) => {
-                                        let allow_list = self.target_modules.get(module_name).unwrap();
-                                        if allow_list.is_empty() || allow_list.contains(&imported_ident.sym) {
-                                            self.target_variables.insert(local.to_id());
-                                        }
-                                    },
-                                    ModuleExportName::Str(imported_ecma_lit_str) => {
-                                        let allow_list = self.target_modules.get(module_name).unwrap();
-                                        if allow_list.is_empty() || allow_list.contains(&imported_ecma_lit_str.value) {
-                                            self.target_variables.insert(local.to_id());
-                                        }
-                                    }
-                                }
-                            },
-                            None => {
-                                let allow_list = self.target_modules.get(module_name).unwrap();
-                                if allow_list.is_empty() || allow_list.contains(&local.sym) {
-                                    self.target_variables.insert(local.to_id());
-                                }
-                            }
+                        let is_allowed = match imported {
+                            Some(ModuleExportName::Ident(imported_ident)) => allow_list.contains(&imported_ident.sym),
+                            Some(ModuleExportName::Str(imported_ecma_lit_str)) => allow_list.contains(&imported_ecma_lit_str.value),
+                            None => allow_list.contains(&local.sym)
+                        };
+                        if allow_list.is_empty() || is_allowed {
+                            self.target_variables.insert(local.to_id(), TargetBinding::imported_from(&n.src, imported_name(imported.as_ref(), local)));
                         }
                     }
                 }
@@ -724,6 +2109,13 @@ impl VisitMut for TransformVisitor {
         n.visit_mut_children_with(self);
     }
 
+    fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+        if n.init.as_deref().is_some_and(is_import_meta_vitest) {
+            self.register_in_source_testing_variables(&n.name);
+        }
+        n.visit_mut_children_with(self);
+    }
+
     fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
         n.visit_mut_children_with(self);
         if !self.has_declarations_and_imports_to_be_inserted() {
@@ -731,6 +2123,9 @@ impl VisitMut for TransformVisitor {
         }
         let mut new_items: Vec<ModuleItem> = Vec::new();
         new_items.push(self.create_power_assert_runtime_import_decl());
+        if !self.assertion_table.is_empty() {
+            new_items.push(ModuleItem::Stmt(self.create_assertion_table_decl()));
+        }
         for assertion_metadata in self.assertion_metadata_vec.iter() {
             new_items.push(ModuleItem::Stmt(self.create_powered_runner_decl(assertion_metadata)));
         }
@@ -743,57 +2138,56 @@ impl VisitMut for TransformVisitor {
         let idx = end_of_import_position;
         n.splice(idx..idx, new_items);
         self.clear_transformations();
+        self.assertion_table.clear();
     }
 
     fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+        // declarations of assertions visited so far belong to the enclosing statements, not to this block
+        let enclosing_transformations = self.take_transformations();
         n.visit_mut_children_with(self);
-        if !self.has_declarations_to_be_inserted() {
-            return;
-        }
-        let mut new_items: Vec<Stmt> = Vec::new();
-        for assertion_metadata in self.assertion_metadata_vec.iter() {
-            new_items.push(self.create_powered_runner_decl(assertion_metadata));
-        }
-        for argument_metadata in self.argument_metadata_vec.iter() {
-            new_items.push(self.create_argrec_decl(argument_metadata));
+        if self.has_declarations_to_be_inserted() {
+            let mut new_items: Vec<Stmt> = Vec::new();
+            for assertion_metadata in self.assertion_metadata_vec.iter() {
+                new_items.push(self.create_powered_runner_decl(assertion_metadata));
+            }
+            for argument_metadata in self.argument_metadata_vec.iter() {
+                new_items.push(self.create_argrec_decl(argument_metadata));
+            }
+            n.splice(0..0, new_items);
         }
-        n.splice(0..0, new_items);
-        self.clear_transformations();
+        self.restore_transformations(enclosing_transformations);
     }
 
     fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
         if self.assertion_metadata.is_some() { // callexp inside assertion
-            n.visit_mut_children_with(self);
+            // assertion in an arrow function passed to the assertion. e.g. assert(x) in assert.throws(() => assert(x))
+            let nested_assertion_callee = match &n.callee {
+                Callee::Expr(expr) if self.arrow_depth > 0 => self.assertion_callee(expr),
+                _ => None
+            };
+            if let Some((prop_name, receiver)) = nested_assertion_callee {
+                let suspended = self.suspend_capture();
+                self.capture_assertion(n, prop_name, receiver, false);
+                self.resume_capture(suspended);
+            } else {
+                n.visit_mut_children_with(self);
+            }
             return;
         }
         // callexp outside assertion
         // if there are no variables that we care about, skip the following transformation logic
-        if self.target_variables.is_empty() {
+        if self.target_variables.is_empty() && self.target_namespaces.is_empty() && self.expect_variables.is_empty() && self.globals.is_empty() {
             n.visit_mut_children_with(self);
             return;
         }
-        let (prop_name, obj_name): (Option<Atom>, Option<Atom>) = match n.callee {
-            Callee::Expr(ref mut expr) => {
-                match expr.as_mut() {
-                    Expr::Member(MemberExpr{ prop: MemberProp::Ident(prop_ident), obj, .. }) => {
-                        match obj.as_ref() {
-                            Expr::Ident(ref obj_ident) if self.target_variables.contains(&obj_ident.to_id()) => {
-                                (Some(prop_ident.sym.clone()), Some(obj_ident.sym.clone()))
-                            },
-                            // Expr::Member
-                            _ => (None, None)
-                        }
-                    },
-                    Expr::Ident(ref ident) if self.target_variables.contains(&ident.to_id()) => {
-                        (Some(ident.sym.clone()), None)
-                    },
-                    _ => (None, None)
-                }
-            },
-            _ => (None, None)
+        let assertion_callee = match &n.callee {
+            Callee::Expr(expr) => self.assertion_callee(expr).or_else(|| self.global_assertion_callee(expr)),
+            _ => None
         };
-        if let Some(prop_name_value) = prop_name {
-            self.capture_assertion(n, prop_name_value, obj_name);
+        if let Some((prop_name, receiver)) = assertion_callee {
+            self.capture_assertion(n, prop_name, receiver, false);
+        } else if let Some(expect_ident) = self.expect_of_matcher_chain(n) {
+            self.capture_assertion(n, expect_ident.sym.clone(), None, true);
         } else {
             n.visit_mut_children_with(self);
         }
@@ -804,7 +2198,32 @@ impl VisitMut for TransformVisitor {
             n.visit_mut_children_with(self);
             return;
         }
-        // skip function
+        // function body is not a part of the assertion, while assertions in it are captured on their own.
+        // e.g. assert(x) in assert.doesNotThrow(function () { assert(x); })
+        let suspended = self.suspend_capture();
+        n.visit_mut_children_with(self);
+        self.resume_capture(suspended);
+    }
+
+    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+        if self.argument_metadata.is_none() {
+            n.visit_mut_children_with(self);
+            return;
+        }
+        match n.body.as_ref() {
+            BlockStmtOrExpr::BlockStmt(_) => {
+                // same as function body. e.g. assert(x) in assert.doesNotThrow(() => { assert(x); })
+                let suspended = self.suspend_capture();
+                n.visit_mut_children_with(self);
+                self.resume_capture(suspended);
+            },
+            _ => {
+                // expression body is captured as a part of the assertion. e.g. fn(arg) in assert.throws(() => fn(arg))
+                self.arrow_depth += 1;
+                n.visit_mut_children_with(self);
+                self.arrow_depth -= 1;
+            }
+        }
     }
 
     fn visit_mut_assign_expr(&mut self, n: &mut AssignExpr) {
@@ -865,22 +2284,19 @@ impl VisitMut for TransformVisitor {
             n.visit_mut_children_with(self);
             return;
         }
-        match &n {
-            Callee::Expr(callee_expr) => {
-                match callee_expr.as_ref() {
-                    Expr::Ident(Ident { .. }) => {
-                        // do not capture foo in foo()
-                    },
-                    Expr::Member(MemberExpr{ .. }) => {
-                        // do not capture foo.bar in foo.bar() or foo[bar] in foo[bar]()
-                        self.do_not_capture_immediate_child = true;
-                        n.visit_mut_children_with(self);
-                        self.do_not_capture_immediate_child = false;
-                    },
-                    _ => n.visit_mut_children_with(self)
-                }
-            },
-            _ => {}
+        if let Callee::Expr(callee_expr) = &n {
+            match callee_expr.as_ref() {
+                Expr::Ident(Ident { .. }) => {
+                    // do not capture foo in foo()
+                },
+                Expr::Member(MemberExpr{ .. }) => {
+                    // do not capture foo.bar in foo.bar() or foo[bar] in foo[bar]()
+                    self.do_not_capture_immediate_child = true;
+                    n.visit_mut_children_with(self);
+                    self.do_not_capture_immediate_child = false;
+                },
+                _ => n.visit_mut_children_with(self)
+            }
         }
     }
 
@@ -895,23 +2311,71 @@ impl VisitMut for TransformVisitor {
                 return;
             },
             Expr::Fn(_) => {
-                // skip function body
+                // function expression itself is not captured. see visit_mut_function for its body
+                n.visit_mut_children_with(self);
                 return;
             },
             _ => {}
         }
-        let do_not_capture_current_expr = self.do_not_capture_immediate_child;
+        let is_binexp_operand = self.assertion_metadata.as_ref().unwrap().binexp_operand_spans.contains(&n.span());
+        let do_not_capture_current_expr = self.do_not_capture_immediate_child || !(self.capture_policy.captures(n) || is_binexp_operand);
         self.do_not_capture_immediate_child = false;
         // calculate assertion relative offset before entering children
-        let assertion_relative_offset = self.calculate_utf16_assertion_relative_offset(n);
+        let assertion_relative_offset = match self.calculate_utf16_assertion_relative_offset(n) {
+            Ok(offset) => offset,
+            Err(reason) => {
+                // give up instrumenting whole assertion
+                self.assertion_metadata.as_mut().unwrap().skip_reason.get_or_insert(reason);
+                return;
+            }
+        };
+        let capture_point = if do_not_capture_current_expr {
+            None
+        } else {
+            Some(self.start_capture(n, assertion_relative_offset))
+        };
         // enter children
         n.visit_mut_children_with(self);
-        if !do_not_capture_current_expr {
-            self.wrap_with_tap(n, &assertion_relative_offset);
+        if let Some(capture_point) = capture_point {
+            self.wrap_with_tap(n, &capture_point);
         }
     }
 }
 
+/// Creates the power-assert transform for native SWC hosts.
+///
+/// `source_map` is the one the program was parsed with, so that assertion
+/// code can be embedded as written. Assertions whose source is not found in it
+/// fall back to code regenerated from the AST.
+///
+/// ```
+/// use swc_core::common::{sync::Lrc, SourceMap};
+/// use swc_plugin_power_assert::{power_assert, Config};
+///
+/// let cm: Lrc<SourceMap> = Default::default();
+/// let pass = power_assert(Config::builder().runtime("./runtime.mjs").build(), cm);
+/// # let _ = pass;
+/// ```
+pub fn power_assert(config: Config, source_map: Lrc<SourceMap>) -> impl Pass {
+    visit_mut_pass(TransformVisitor {
+        source_map: Some(source_map),
+        .. TransformVisitor::new(&config)
+    })
+}
+
+/// Creates the power-assert transform for programs the resolver has run on.
+///
+/// `unresolved_mark` is the one given to the resolver, with which
+/// [`Config::globals`] are told from local variables of the same name.
+/// Globals are not instrumented by [`power_assert`].
+pub fn power_assert_with_unresolved_mark(config: Config, source_map: Lrc<SourceMap>, unresolved_mark: Mark) -> impl Pass {
+    visit_mut_pass(TransformVisitor {
+        source_map: Some(source_map),
+        unresolved_mark: Some(unresolved_mark),
+        .. TransformVisitor::new(&config)
+    })
+}
+
 /// An example plugin function with macro support.
 /// `plugin_transform` macro interop pointers into deserialized structs, as well
 /// as returning ptr back to host.
@@ -927,77 +2391,386 @@ impl VisitMut for TransformVisitor {
 ///
 /// This requires manual handling of serialization / deserialization from ptrs.
 /// Refer swc_plugin_macro to see how does it work internally.
+#[cfg(feature = "plugin")]
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
-    program.apply(&mut visit_mut_pass(TransformVisitor::from(metadata)))
+    match TransformVisitor::from_plugin_metadata(metadata) {
+        Some(visitor) => program.apply(&mut visit_mut_pass(visitor)),
+        None => program
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
-    use swc_ecma_transforms_testing::test_fixture;
-    use swc_core::ecma::transforms::testing::FixtureTestConfig;
-    use swc_core::ecma::visit::visit_mut_pass;
-    use swc_ecma_parser::{EsSyntax, Syntax};
     use std::fs;
-    use super::TransformVisitor;
+    use std::path::{Path, PathBuf};
+    use serde::Deserialize;
+    use swc_ecma_transforms_testing::{test_fixture, test_inline, FixtureTestConfig, Tester};
+    use swc_core::ecma::visit::visit_mut_pass;
+    use swc_ecma_parser::{EsSyntax, Syntax, TsSyntax};
+    use std::sync::{Arc, Mutex};
+    use swc_core::common::{DUMMY_SP, FileName, Globals, SourceMapper, Span, GLOBALS};
+    use swc_core::common::errors::{DiagnosticBuilder, Emitter, Handler, HANDLER};
+    use swc_core::ecma::ast::Ident;
+    use swc_core::ecma::visit::VisitMut;
+    use std::time::Instant;
+    use swc_core::common::{SourceMap, sync::Lrc};
+    use swc_core::ecma::ast::{EsVersion, Pass, Program};
+    use swc_core::ecma::visit::VisitMutWith;
+    use swc_ecma_parser::{parse_file_as_module, parse_file_as_program};
+    use swc_ecma_transforms_base::resolver;
+    use swc_core::common::Mark;
+    use swc_core::common::source_map::SmallPos;
+    use super::{
+        power_assert,
+        power_assert_with_unresolved_mark,
+        CalleePattern,
+        Config,
+        TransformVisitor,
+        Utf16PosIndex,
+        Utf8Pos,
+        glob_to_regex
+    };
+
+    /// `options.json` placed next to the fixture
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+    struct FixtureOptions {
+        /// "ecmascript" (default) or "typescript"
+        syntax: Option<String>,
+        jsx: bool,
+        tsx: bool,
+        /// parsed as Script if false, auto-detected if not specified
+        is_module: Option<bool>,
+        /// plugin options, same as the ones in `.swcrc`
+        plugin: Config
+    }
 
-    #[testing::fixture("tests/fixtures/*/fixture.mjs")]
-    fn test_with_fixtures(input: PathBuf) {
-        let output = input.with_file_name("expected.mjs");
-        let code = fs::read_to_string(&input).unwrap();
-        test_fixture(
-            Syntax::Es(EsSyntax::default()),
-            &|_t| {
-                visit_mut_pass(TransformVisitor::from(&code))
-            },
-            &input,
-            &output,
+    impl FixtureOptions {
+        fn load(input: &Path) -> Self {
+            let options_path = input.with_file_name("options.json");
+            if !options_path.exists() {
+                return FixtureOptions::default();
+            }
+            let options_json = fs::read_to_string(&options_path).unwrap();
+            serde_json::from_str(&options_json).unwrap_or_else(|err| panic!("invalid {}: {}", options_path.display(), err))
+        }
+
+        fn syntax(&self) -> Syntax {
+            match self.syntax.as_deref() {
+                None | Some("ecmascript") => Syntax::Es(EsSyntax { jsx: self.jsx, ..Default::default() }),
+                Some("typescript") => Syntax::Typescript(TsSyntax { tsx: self.tsx, ..Default::default() }),
+                Some(syntax) => panic!("unknown syntax: {}", syntax)
+            }
+        }
+
+        // resolves identifiers beforehand as SWC does, so that globals can be told from local variables
+        fn pass(&self, t: &Tester) -> impl Pass {
+            let unresolved_mark = Mark::new();
+            (
+                resolver(unresolved_mark, Mark::new(), self.syntax.as_deref() == Some("typescript")),
+                visit_mut_pass(TransformVisitor {
+                    source_map: Some(t.cm.clone()),
+                    unresolved_mark: Some(unresolved_mark),
+                    .. TransformVisitor::new(&self.plugin)
+                })
+            )
+        }
+
+        fn test_config(&self) -> FixtureTestConfig {
             FixtureTestConfig {
                 allow_error: true,
+                module: self.is_module,
                 ..Default::default()
+            }
+        }
+    }
+
+    // fixture.mjs -> expected.mjs, fixture.nosrc.mjs -> expected.nosrc.mjs, and so on
+    fn expected_path(input: &Path, infix: &str) -> PathBuf {
+        let extension = input.extension().unwrap().to_str().unwrap();
+        input.with_file_name(format!("expected.{}{}", infix, extension))
+    }
+
+    // emulates an earlier transform that synthesizes nodes without source positions
+    struct SynthesizeSpans;
+
+    impl VisitMut for SynthesizeSpans {
+        fn visit_mut_ident(&mut self, n: &mut Ident) {
+            if n.sym == "synthesized" {
+                n.span = DUMMY_SP;
+            }
+        }
+    }
+
+    test_inline!(
+        Syntax::Es(EsSyntax::default()),
+        |t| power_assert(Config::builder().runtime("./runtime.mjs").modules(["my-assert"]).build(), t.cm.clone()),
+        test_power_assert_with_config,
+        r#"import assert from 'node:assert';
+import ok from 'my-assert';
+assert(truthy);
+ok(truthy);"#,
+        r#"import assert from 'node:assert';
+import ok from 'my-assert';
+import { _power_ } from "./runtime.mjs";
+const _pasrt1 = _power_(ok, null, "ok(truthy)");
+const _parg1 = _pasrt1.recorder(0);
+assert(truthy);
+_pasrt1.run(_parg1.rec(truthy, 3, 3, 9));"#
+    );
+
+    test_inline!(
+        Syntax::Es(EsSyntax::default()),
+        |t| {
+            let unresolved_mark = Mark::new();
+            (
+                resolver(unresolved_mark, Mark::new(), false),
+                power_assert_with_unresolved_mark(Config::builder().global("assert").global("console.assert").build(), t.cm.clone(), unresolved_mark)
+            )
+        },
+        test_globals,
+        r#"import { it } from 'node:test';
+assert(truthy);
+assert.equal(foo.bar, baz);
+console.assert(items.length > 0, 'no items');
+function localAssert(assert, console) {
+    assert(truthy);
+    console.assert(truthy);
+}"#,
+        r#"import { it } from 'node:test';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert, null, "assert(truthy)");
+const _pasrt2 = _power_(assert.equal, assert, "assert.equal(foo.bar, baz)");
+const _pasrt3 = _power_(console.assert, console, "console.assert(items.length > 0, 'no items')");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.recorder(1);
+const _parg4 = _pasrt3.recorder(0);
+_pasrt1.run(_parg1.rec(truthy, 7, 7, 13));
+_pasrt2.run(_parg2.rec(_parg2.tap(foo, 13, 13, 16).bar, 17, 13, 20), _parg3.rec(baz, 22, 22, 25));
+_pasrt3.run(_parg4.rec(_parg4.tap(_parg4.tap(items, 15, 15, 20).length, 21, 15, 27) > _parg4.tap(0, 30, 30, 31), 28, 15, 31), 'no items');
+function localAssert(assert1, console1) {
+    assert1(truthy);
+    console1.assert(truthy);
+}"#
+    );
+
+    test_inline!(
+        Syntax::Es(EsSyntax::default()),
+        |t| power_assert(Config::builder().global("assert").build(), t.cm.clone()),
+        test_globals_without_unresolved_mark,
+        r#"assert(truthy);"#,
+        r#"assert(truthy);"#
+    );
+
+    test_inline!(
+        Syntax::Es(EsSyntax::default()),
+        |t| power_assert(Config::builder().callee_pattern(CalleePattern::new("^assert[A-Z]").unwrap()).build(), t.cm.clone()),
+        test_callee_patterns_without_target_modules,
+        r#"import { parse } from './parser.js';
+assertPositive(parse(input).length);
+function assertPositive (n) {
+    if (n <= 0) throw new Error('not positive');
+}"#,
+        r#"import { parse } from './parser.js';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assertPositive, null, "assertPositive(parse(input).length)");
+const _parg1 = _pasrt1.recorder(0);
+_pasrt1.run(_parg1.rec(_parg1.tap(parse(_parg1.tap(input, 21, 21, 26)), 15, 15, 27).length, 28, 15, 34));
+function assertPositive(n) {
+    if (n <= 0) throw new Error('not positive');
+}"#
+    );
+
+    // handlers of the fixture tests do not emit warnings, so they are collected here
+    struct WarningCollector(Arc<Mutex<Vec<(String, Span)>>>);
+
+    impl Emitter for WarningCollector {
+        fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+            self.0.lock().unwrap().push((db.message(), db.span.primary_span().unwrap()));
+        }
+    }
+
+    fn collect_warnings(config: Config, code: &str) -> Vec<(String, String)> {
+        collect_warnings_of(code, |cm| power_assert(config, cm))
+    }
+
+    fn collect_warnings_of<P: Pass>(code: &str, pass: impl FnOnce(Lrc<SourceMap>) -> P) -> Vec<(String, String)> {
+        GLOBALS.set(&Globals::new(), || {
+            let cm: Lrc<SourceMap> = Default::default();
+            let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+            // parsed as Script unless the code has imports or exports
+            let program = parse_file_as_program(&fm, Syntax::Es(EsSyntax::default()), EsVersion::latest(), None, &mut vec![]).unwrap();
+            let warnings = Arc::new(Mutex::new(Vec::new()));
+            let handler = Handler::with_emitter(true, false, Box::new(WarningCollector(warnings.clone())));
+            HANDLER.set(&handler, || {
+                program.apply(pass(cm.clone()));
+            });
+            let warnings = warnings.lock().unwrap();
+            warnings.iter().map(|(message, span)| (message.clone(), cm.span_to_snippet(*span).unwrap())).collect()
+        })
+    }
+
+    #[cfg(feature = "plugin")]
+    struct ErrorCollector(Arc<Mutex<Vec<String>>>);
+
+    #[cfg(feature = "plugin")]
+    impl Emitter for ErrorCollector {
+        fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+            self.0.lock().unwrap().push(db.message());
+        }
+    }
+
+    #[cfg(feature = "plugin")]
+    #[test]
+    fn test_report_invalid_plugin_options() {
+        let errors = Arc::new(Mutex::new(Vec::new()));
+        let handler = Handler::with_emitter(true, false, Box::new(ErrorCollector(errors.clone())));
+        HANDLER.set(&handler, || {
+            assert!(super::plugin_config(None).is_some());
+            assert!(super::plugin_config(Some(r#"{ "runtime": "./runtime.mjs" }"#)).is_some());
+            assert!(super::plugin_config(Some(r#"{ "calleePatterns": ["^assert("] }"#)).is_none());
+        });
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("power-assert: invalid options for swc-plugin-power-assert: "), "{}", errors[0]);
+        assert!(errors[0].contains("unclosed group"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn test_warn_side_effects() {
+        let warnings = collect_warnings(Config::builder().warn_side_effects(true).build(), r#"import assert from 'node:assert';
+assert(queue.shift() === 1);
+assert(i++ < 3);
+assert.equal(delete obj.prop, true);
+assert.deepEqual(map.set(key, value = 1), expected);
+assert.throws(() => { state.count += 1; });
+assert(ary.indexOf(zero) === two);
+"#);
+        let snippets: Vec<&str> = warnings.iter().map(|(_, snippet)| snippet.as_str()).collect();
+        assert_eq!(snippets, ["queue.shift()", "i++", "delete obj.prop", "map.set(key, value = 1)", "value = 1"]);
+        assert_eq!(warnings[0].0, "power-assert: call to mutating method `shift` changes state, so the assertion behaves differently when stripped or re-run");
+        assert_eq!(warnings[1].0, "power-assert: increment or decrement changes state, so the assertion behaves differently when stripped or re-run");
+    }
+
+    #[test]
+    fn test_warn_skipped_assertion() {
+        let warnings = collect_warnings_of(r#"import assert from 'node:assert/strict';
+assert(foo === synthesized);
+assert(foo === bar);
+"#, |cm| (
+            visit_mut_pass(SynthesizeSpans),
+            visit_mut_pass(TransformVisitor {
+                source_map: Some(cm),
+                filename: Some("test/synthesized.test.mjs".into()),
+                .. TransformVisitor::new(&Config::default())
+            })
+        ));
+        assert_eq!(warnings, [(
+            "power-assert: assertion in test/synthesized.test.mjs is left uninstrumented because the assertion contains a node without source position".to_string(),
+            "assert(foo === synthesized)".to_string()
+        )]);
+    }
+
+    #[test]
+    fn test_warn_assertions_in_script() {
+        let warnings = collect_warnings_of("console.assert(x > 1);\nconsole.log(x);\n", |cm| {
+            let unresolved_mark = Mark::new();
+            (
+                resolver(unresolved_mark, Mark::new(), false),
+                power_assert_with_unresolved_mark(Config::builder().global("console.assert").build(), cm, unresolved_mark)
+            )
+        });
+        assert_eq!(warnings, [(
+            "power-assert: assertion is left uninstrumented because the file is parsed as a script, which cannot import the runtime".to_string(),
+            "console.assert(x > 1)".to_string()
+        )]);
+    }
+
+    #[test]
+    fn test_do_not_warn_side_effects_by_default() {
+        let warnings = collect_warnings(Config::default(), r#"import assert from 'node:assert';
+assert(queue.shift() === 1);
+"#);
+        assert!(warnings.is_empty());
+    }
+
+    #[testing::fixture("tests/fixtures/*/fixture.*", exclude(r"fixture\.\w+\.\w+$"))]
+    fn test_with_fixtures(input: PathBuf) {
+        let options = FixtureOptions::load(&input);
+        test_fixture(
+            options.syntax(),
+            &|t| {
+                options.pass(t)
             },
+            &input,
+            &expected_path(&input, ""),
+            options.test_config(),
         );
     }
 
-    #[testing::fixture("tests/fixtures/*/fixture.cond.mjs")]
-    fn test_with_fixtures_for_swc(input: PathBuf) {
-        let output = input.with_file_name("expected.swc.mjs");
-        let code = fs::read_to_string(&input).unwrap();
+    #[testing::fixture("tests/fixtures/*/fixture.*", exclude(r"fixture\.\w+\.\w+$"))]
+    fn test_with_fixtures_preceded_by_another_file(input: PathBuf) {
+        let options = FixtureOptions::load(&input);
         test_fixture(
-            Syntax::Es(EsSyntax::default()),
-            &|_t| {
-                visit_mut_pass(TransformVisitor::from(&code))
+            options.syntax(),
+            &|t| {
+                // like bundlers do, load another file into the same SourceMap beforehand
+                // so that spans of the fixture do not start at the beginning
+                t.cm.new_source_file(FileName::Anon.into(), "const preceding = 'ほげ';\n");
+                options.pass(t)
             },
             &input,
-            &output,
-            FixtureTestConfig {
-                allow_error: true,
-                ..Default::default()
-            },
+            &expected_path(&input, ""),
+            options.test_config(),
         );
     }
 
-    #[test]
-    fn test_relative_path_to_sandbox_path() {
-        let input = "examples/bowling.test.mjs".to_string();
-        let cwd = "/Users/takuto/src/github.com/twada/power-assert-monorepo/packages/swc-plugin-power-assert".to_string();
-        assert_eq!(super::resolve_path_in_sandbox(&input, &cwd), "/cwd/examples/bowling.test.mjs");
+    #[testing::fixture("tests/fixtures/*/fixture.cond.*")]
+    fn test_with_fixtures_for_swc(input: PathBuf) {
+        let options = FixtureOptions::load(&input);
+        test_fixture(
+            options.syntax(),
+            &|t| {
+                options.pass(t)
+            },
+            &input,
+            &expected_path(&input, "swc."),
+            options.test_config(),
+        );
     }
 
-    #[test]
-    fn test_absolute_path_to_sandbox_path() {
-        let input = "/Users/takuto/src/github.com/twada/power-assert-monorepo/packages/swc-plugin-power-assert/examples/bowling.test.mjs".to_string();
-        let cwd = "/Users/takuto/src/github.com/twada/power-assert-monorepo/packages/swc-plugin-power-assert".to_string();
-        assert_eq!(super::resolve_path_in_sandbox(&input, &cwd), "/cwd/examples/bowling.test.mjs");
+    #[testing::fixture("tests/fixtures/*/fixture.nosrc.*")]
+    fn test_with_fixtures_without_source(input: PathBuf) {
+        let options = FixtureOptions::load(&input);
+        test_fixture(
+            options.syntax(),
+            &|_t| {
+                visit_mut_pass(TransformVisitor::new(&options.plugin))
+            },
+            &input,
+            &expected_path(&input, "nosrc."),
+            options.test_config(),
+        );
     }
 
-    #[test]
-    fn test_file_url_to_sandbox_path() {
-        let input = "file:///Users/takuto/src/github.com/twada/power-assert-monorepo/packages/swc-plugin-power-assert/examples/bowling.test.mjs".to_string();
-        let cwd = "/Users/takuto/src/github.com/twada/power-assert-monorepo/packages/swc-plugin-power-assert".to_string();
-        assert_eq!(super::resolve_path_in_sandbox(&input, &cwd), "/cwd/examples/bowling.test.mjs");
+    #[testing::fixture("tests/fixtures/*/fixture.synth.*")]
+    fn test_with_fixtures_with_synthesized_spans(input: PathBuf) {
+        let options = FixtureOptions::load(&input);
+        test_fixture(
+            options.syntax(),
+            &|t| {
+                (
+                    visit_mut_pass(SynthesizeSpans),
+                    options.pass(t)
+                )
+            },
+            &input,
+            &expected_path(&input, "synth."),
+            options.test_config(),
+        );
     }
 
     #[test]
@@ -1014,4 +2787,98 @@ mod tests {
         assert_eq!(third_char.len_utf16(), 1);
         assert_eq!(third_char.len_utf8(), 3);
     }
+
+    #[test]
+    fn test_utf16_pos_index() {
+        let code = "a🍣bかc𠮷d";
+        let index = Utf16PosIndex::new(code);
+        for utf8_pos in (0..=code.len()).filter(|pos| code.is_char_boundary(*pos)) {
+            let expected = code[..utf8_pos].encode_utf16().count();
+            assert_eq!(index.lookup(Utf8Pos::from_usize(utf8_pos)).to_usize(), expected);
+        }
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        let glob = glob_to_regex("**/test-utils/*.js");
+        assert!(glob.is_match("./test-utils/assert.js"));
+        assert!(glob.is_match("../../test-utils/assert.js"));
+        assert!(glob.is_match("test-utils/assert.js"));
+        assert!(!glob.is_match("./test-utils/nested/assert.js"));
+        assert!(!glob.is_match("./test-utils/assert.mjs"));
+        let glob = glob_to_regex("./helpers/assert?.js");
+        assert!(glob.is_match("./helpers/assert2.js"));
+        assert!(!glob.is_match("./helpers/assert.js"));
+        assert!(!glob.is_match("xhelpers/assert2.js"));
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+    fn bench_utf16_pos_lookup_on_large_multibyte_assertion() {
+        // table-driven assertion full of Japanese and emoji
+        let rows: Vec<String> = (0..2000).map(|i| format!("['テスト{}', '🍣🍺{}']", i, i)).collect();
+        let code = format!("assert.deepEqual(table, [{}])", rows.join(", "));
+        let positions: Vec<usize> = (0..=code.len()).filter(|pos| code.is_char_boundary(*pos)).step_by(3).collect();
+
+        let started = Instant::now();
+        let scanned: Vec<usize> = positions.iter().map(|pos| code[..*pos].encode_utf16().count()).collect();
+        let scan_elapsed = started.elapsed();
+
+        let started = Instant::now();
+        let index = Utf16PosIndex::new(&code);
+        let looked_up: Vec<usize> = positions.iter().map(|pos| index.lookup(Utf8Pos::from_usize(*pos)).to_usize()).collect();
+        let index_elapsed = started.elapsed();
+
+        assert_eq!(scanned, looked_up);
+        println!("{} lookups on {} bytes: scan from start {:?}, indexed {:?}", positions.len(), code.len(), scan_elapsed, index_elapsed);
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with `cargo test --release -- --ignored --nocapture`"]
+    fn bench_bail_out_on_large_bundle_without_assertions() {
+        let functions: Vec<String> = (0..20000).map(|i| format!("export function f{i} (a, b) {{ return g(a.b[{i}], b) === h(a) ? [a, {{ b }}] : `${{a}}{i}`; }}")).collect();
+        let code = format!("import {{ g, h }} from './lib.mjs';\n{}\n", functions.join("\n"));
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), code);
+        let module = parse_file_as_module(&fm, Syntax::Es(EsSyntax::default()), EsVersion::latest(), None, &mut vec![]).unwrap();
+
+        let mut program = Program::Module(module.clone());
+        let started = Instant::now();
+        program.visit_mut_children_with(&mut TransformVisitor::from(cm.clone()));
+        let traversal_elapsed = started.elapsed();
+
+        let mut program = Program::Module(module.clone());
+        let started = Instant::now();
+        program.visit_mut_with(&mut TransformVisitor::from(cm.clone()));
+        let bail_out_elapsed = started.elapsed();
+
+        // globals and callee patterns cannot be told from imports, so the bundle is scanned once without transformation
+        let (scanned_traversal_elapsed, prescan_elapsed) = GLOBALS.set(&Globals::new(), || {
+            let unresolved_mark = Mark::new();
+            let program = Program::Module(module).apply(resolver(unresolved_mark, Mark::new(), false));
+            let config = Config::builder()
+                .global("assert")
+                .global("console.assert")
+                .callee_pattern(CalleePattern::new("^assert[A-Z]").unwrap())
+                .build();
+            let visitor = || TransformVisitor {
+                source_map: Some(cm.clone()),
+                unresolved_mark: Some(unresolved_mark),
+                .. TransformVisitor::new(&config)
+            };
+
+            let mut traversed = program.clone();
+            let started = Instant::now();
+            traversed.visit_mut_children_with(&mut visitor());
+            let traversal_elapsed = started.elapsed();
+
+            let mut scanned = program;
+            let started = Instant::now();
+            scanned.visit_mut_with(&mut visitor());
+            (traversal_elapsed, started.elapsed())
+        });
+
+        println!("{} bytes bundle: full traversal {:?}, bail out {:?}", fm.src.len(), traversal_elapsed, bail_out_elapsed);
+        println!("with globals and callee patterns: full traversal {:?}, bail out after scan {:?}", scanned_traversal_elapsed, prescan_elapsed);
+    }
 }
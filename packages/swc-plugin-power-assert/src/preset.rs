@@ -0,0 +1,102 @@
+use serde::Deserialize;
+use crate::config::ModuleSpecifier;
+use crate::{ArgumentRoles, CHAI_ASSERT_ARGUMENTS, NODE_ASSERT_ARGUMENTS};
+
+/// Assertion library whose modules and argument roles are known to the transform.
+///
+/// Presets add to the target modules instead of replacing them, so
+/// `node:assert` and the others in [`Config::default`](crate::Config) stay targets.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub enum Preset {
+    /// `assert` of chai
+    #[serde(rename = "chai")]
+    Chai,
+    /// `uvu/assert`
+    #[serde(rename = "uvu/assert")]
+    Uvu,
+    /// `@std/assert` and `jsr:@std/assert` of Deno
+    #[serde(rename = "@std/assert")]
+    StdAssert,
+    /// `expect` of `bun:test`
+    #[serde(rename = "bun:test")]
+    BunTest,
+    /// legacy `power-assert` module, which has the API of `node:assert`
+    #[serde(rename = "power-assert")]
+    PowerAssert,
+    /// `tiny-invariant` and `invariant`
+    #[serde(rename = "invariant")]
+    Invariant
+}
+
+impl Preset {
+    pub(crate) fn modules(&self) -> Vec<ModuleSpecifier> {
+        match self {
+            Preset::Chai => vec![ModuleSpecifier::with_imports("chai", ["assert"])],
+            Preset::Uvu => vec!["uvu/assert".into()],
+            Preset::StdAssert => vec!["@std/assert".into(), "jsr:@std/assert".into()],
+            // bun:test has no assert, expect is instrumented instead
+            Preset::BunTest => vec![],
+            Preset::PowerAssert => vec!["power-assert".into()],
+            Preset::Invariant => vec!["tiny-invariant".into(), "invariant".into()]
+        }
+    }
+
+    // modules whose expect is instrumented, regardless of the expect option
+    pub(crate) fn expect_modules(&self) -> &'static [&'static str] {
+        match self {
+            Preset::BunTest => &["bun:test"],
+            _ => &[]
+        }
+    }
+
+    // indexes of instrumented arguments per function of the modules, where `default` is the default export
+    pub(crate) fn captured_arguments(&self) -> ArgumentRoles {
+        match self {
+            Preset::Chai => &CHAI_ASSERT_ARGUMENTS,
+            Preset::PowerAssert => &NODE_ASSERT_ARGUMENTS,
+            Preset::Uvu => &[
+                ("ok", &[0]),
+                ("is", &[0, 1]),
+                ("equal", &[0, 1]),
+                ("type", &[0]),
+                ("instance", &[0]),
+                ("match", &[0, 1]),
+                ("snapshot", &[0, 1]),
+                ("fixture", &[0, 1]),
+                ("throws", &[0]),
+                ("unreachable", &[])
+            ],
+            Preset::StdAssert => &[
+                ("assert", &[0]),
+                ("assertFalse", &[0]),
+                ("assertExists", &[0]),
+                ("assertEquals", &[0, 1]),
+                ("assertNotEquals", &[0, 1]),
+                ("assertStrictEquals", &[0, 1]),
+                ("assertNotStrictEquals", &[0, 1]),
+                ("assertAlmostEquals", &[0, 1]),
+                ("assertGreater", &[0, 1]),
+                ("assertGreaterOrEqual", &[0, 1]),
+                ("assertLess", &[0, 1]),
+                ("assertLessOrEqual", &[0, 1]),
+                ("assertInstanceOf", &[0]),
+                ("assertNotInstanceOf", &[0]),
+                ("assertIsError", &[0]),
+                ("assertMatch", &[0, 1]),
+                ("assertNotMatch", &[0, 1]),
+                ("assertStringIncludes", &[0, 1]),
+                ("assertArrayIncludes", &[0, 1]),
+                ("assertObjectMatch", &[0, 1]),
+                ("assertThrows", &[0]),
+                ("assertRejects", &[0]),
+                ("fail", &[]),
+                ("unreachable", &[])
+            ],
+            // default exports called as invariant(condition, message)
+            Preset::Invariant => &[
+                ("default", &[0])
+            ],
+            Preset::BunTest => &[]
+        }
+    }
+}
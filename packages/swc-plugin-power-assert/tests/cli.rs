@@ -0,0 +1,81 @@
+//! Runs the `swc-power-assert` binary on the files under `tests/cli`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn fixture_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("cli")
+}
+
+// fresh directory per test, since tests run in parallel
+fn out_dir(name: &str) -> PathBuf {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli").join(name);
+    let _ = fs::remove_dir_all(&out_dir);
+    out_dir
+}
+
+fn swc_power_assert(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_swc-power-assert"))
+        .args(args)
+        .output()
+        .expect("failed to run swc-power-assert")
+}
+
+#[test]
+fn test_instrument_directory_with_source_maps() {
+    let out_dir = out_dir("directory");
+    let output = swc_power_assert(&[Path::new("--out-dir"), &out_dir, &fixture_dir()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let code = fs::read_to_string(out_dir.join("assert.test.mjs")).unwrap();
+    assert!(code.contains(r#"import { _power_ } from "@power-assert/runtime";"#), "{}", code);
+    assert!(code.contains(r#"_power_(assert, null, "assert(truthy === falsy)""#), "{}", code);
+    assert!(code.ends_with("\n//# sourceMappingURL=assert.test.mjs.map\n"), "{}", code);
+
+    let source_map: serde_json::Value = serde_json::from_slice(&fs::read(out_dir.join("assert.test.mjs.map")).unwrap()).unwrap();
+    assert_eq!(source_map["version"], 3);
+    let source = fs::canonicalize(fixture_dir().join("assert.test.mjs")).unwrap();
+    assert_eq!(source_map["sources"], serde_json::json!([source.to_str().unwrap()]));
+    assert_eq!(source_map["sourcesContent"], serde_json::json!([fs::read_to_string(&source).unwrap()]));
+    assert!(!source_map["mappings"].as_str().unwrap().is_empty());
+
+    // TypeScript is instrumented as well, while declaration files are skipped
+    let code = fs::read_to_string(out_dir.join("nested").join("equal.test.ts")).unwrap();
+    assert!(code.contains(r#"_power_(equal, null, "equal(foo.bar, 2)")"#), "{}", code);
+    assert!(out_dir.join("nested").join("equal.test.ts.map").is_file());
+    assert!(!out_dir.join("nested").join("types.d.ts").exists());
+}
+
+#[test]
+fn test_print_single_file_to_stdout() {
+    let output = swc_power_assert(&[&fixture_dir().join("assert.test.mjs")]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let code = String::from_utf8(output.stdout).unwrap();
+    assert!(code.contains(r#"_power_(assert, null, "assert(truthy === falsy)""#), "{}", code);
+    assert!(!code.contains("sourceMappingURL"), "{}", code);
+}
+
+#[test]
+fn test_require_out_dir_for_multiple_files() {
+    let output = swc_power_assert(&[&fixture_dir()]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--out-dir is required to instrument 2 files"));
+}
+
+#[test]
+fn test_reject_out_dir_overwriting_inputs() {
+    let input_dir = out_dir("overwrite");
+    fs::create_dir_all(&input_dir).unwrap();
+    let input = input_dir.join("assert.test.mjs");
+    fs::copy(fixture_dir().join("assert.test.mjs"), &input).unwrap();
+    let original = fs::read_to_string(&input).unwrap();
+
+    // the same directory spelled differently
+    let output = swc_power_assert(&[Path::new("--out-dir"), &input_dir.join("."), &input_dir]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("would be overwritten by its instrumented output"));
+    assert_eq!(fs::read_to_string(&input).unwrap(), original);
+    assert!(!input_dir.join("assert.test.mjs.map").exists());
+}
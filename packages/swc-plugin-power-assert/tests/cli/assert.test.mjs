@@ -0,0 +1,3 @@
+import assert from 'node:assert/strict';
+
+assert(truthy === falsy);
@@ -0,0 +1,4 @@
+import { equal } from 'node:assert';
+
+const foo: { bar: number } = { bar: 1 };
+equal(foo.bar, 2);
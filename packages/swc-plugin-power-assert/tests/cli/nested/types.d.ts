@@ -0,0 +1 @@
+declare const truthy: unknown;
@@ -0,0 +1,217 @@
+//! Runs the SWC transform over the fixtures of transpiler-core and compares
+//! what gets captured with transpiler-core's expected outputs.
+//!
+//! Outputs are not compared as text. Each assertion is normalized into its
+//! assertion code and the set of `tap` / `rec` positions, so that variable
+//! naming, declaration order, formatting and metadata do not matter.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use rustc_hash::FxHashMap;
+use swc_core::common::{sync::Lrc, FileName, SourceMap};
+use swc_core::ecma::ast::{
+    CallExpr,
+    Callee,
+    EsVersion,
+    Expr,
+    Lit,
+    MemberProp,
+    Pat,
+    Pass,
+    Program,
+    VarDeclarator
+};
+use swc_core::ecma::atoms::Atom;
+use swc_core::ecma::visit::{Visit, VisitWith};
+use swc_ecma_parser::{parse_file_as_module, EsSyntax, Syntax};
+use swc_plugin_power_assert::{power_assert, Config};
+
+/// Fixtures where the two implementations are known to capture differently.
+/// The test fails if one of them starts to agree, so that this list stays accurate.
+const KNOWN_DIFFERENCES: &[(&str, &str)] = &[
+    ("ArrowFunctionExpression", "transpiler-core leaves assertions whose argument is a function as is, while swc records the function and captures inside its body"),
+    ("ClassExpression", "transpiler-core leaves assertions whose argument is a class as is, while swc records the class"),
+    ("TaggedTemplateExpression", "swc captures the tag of tagged templates, transpiler-core does not"),
+    ("YieldExpression", "swc captures the argument of a parenthesized yield, transpiler-core does not"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Capture {
+    // "tap" or "rec"
+    method: String,
+    // markerPos, startPos and endPos, empty for `rec` without positions
+    positions: Vec<u32>
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct NormalizedAssertion {
+    code: String,
+    captures: BTreeSet<Capture>
+}
+
+#[derive(Default)]
+struct CaptureCollector {
+    assertions: Vec<NormalizedAssertion>,
+    // `_pasrt1` -> index of assertions
+    runners: FxHashMap<Atom, usize>,
+    // `_parg1` -> index of assertions
+    recorders: FxHashMap<Atom, usize>,
+    // declarations are collected first, since they are not always placed before their usages
+    collecting_captures: bool
+}
+
+impl CaptureCollector {
+    fn collect(program: &Program) -> Vec<NormalizedAssertion> {
+        let mut collector = CaptureCollector::default();
+        program.visit_with(&mut collector);
+        collector.collecting_captures = true;
+        program.visit_with(&mut collector);
+        collector.assertions
+    }
+}
+
+fn as_ident_sym(expr: &Expr) -> Option<&Atom> {
+    match expr {
+        Expr::Ident(ident) => Some(&ident.sym),
+        _ => None
+    }
+}
+
+fn as_member_call(n: &CallExpr) -> Option<(&Atom, &Atom)> {
+    let Callee::Expr(callee) = &n.callee else {
+        return None;
+    };
+    match callee.as_ref() {
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(prop) => as_ident_sym(&member.obj).map(|obj| (obj, &prop.sym)),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+fn as_u32(expr: &Expr) -> Option<u32> {
+    match expr {
+        Expr::Lit(Lit::Num(num)) => Some(num.value as u32),
+        _ => None
+    }
+}
+
+impl Visit for CaptureCollector {
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        n.visit_children_with(self);
+        if self.collecting_captures {
+            return;
+        }
+        let (Pat::Ident(binding), Some(init)) = (&n.name, &n.init) else {
+            return;
+        };
+        let Expr::Call(call) = init.as_ref() else {
+            return;
+        };
+        // const _pasrt1 = _power_(assert, null, "assert(truthy)");
+        if let Callee::Expr(callee) = &call.callee {
+            if as_ident_sym(callee).is_some_and(|sym| sym == "_power_") {
+                let code = match call.args.get(2).map(|arg| arg.expr.as_ref()) {
+                    Some(Expr::Lit(Lit::Str(code))) => code.value.to_string_lossy().into_owned(),
+                    _ => panic!("assertion code not found in _power_ call")
+                };
+                self.runners.insert(binding.id.sym.clone(), self.assertions.len());
+                self.assertions.push(NormalizedAssertion { code, captures: BTreeSet::new() });
+                return;
+            }
+        }
+        // const _parg1 = _pasrt1.recorder(0);
+        if let Some((obj, prop)) = as_member_call(call) {
+            if prop == "recorder" {
+                if let Some(&idx) = self.runners.get(obj) {
+                    self.recorders.insert(binding.id.sym.clone(), idx);
+                }
+            }
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        n.visit_children_with(self);
+        if !self.collecting_captures {
+            return;
+        }
+        // _parg1.tap(truthy, 7, 7, 13) or _parg1.rec(truthy, 7, 7, 13)
+        let Some((obj, prop)) = as_member_call(n) else {
+            return;
+        };
+        if prop != "tap" && prop != "rec" {
+            return;
+        }
+        let Some(&idx) = self.recorders.get(obj) else {
+            return;
+        };
+        let positions = n.args.iter().skip(1).take(3).filter_map(|arg| as_u32(&arg.expr)).collect();
+        self.assertions[idx].captures.insert(Capture {
+            method: prop.to_string(),
+            positions
+        });
+    }
+}
+
+fn parse(cm: &Lrc<SourceMap>, path: &Path) -> Program {
+    let fm = cm.load_file(path).unwrap();
+    let module = parse_file_as_module(&fm, Syntax::Es(EsSyntax::default()), EsVersion::latest(), None, &mut vec![])
+        .unwrap_or_else(|err| panic!("failed to parse {}: {:?}", path.display(), err));
+    Program::Module(module)
+}
+
+fn transpiler_core_fixtures() -> Vec<PathBuf> {
+    let fixtures_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../transpiler-core/fixtures");
+    let mut fixture_dirs: Vec<PathBuf> = fs::read_dir(fixtures_dir).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join("fixture.mjs").exists() && path.join("expected.mjs").exists())
+        .collect();
+    fixture_dirs.sort();
+    fixture_dirs
+}
+
+fn describe_difference(expected: &[NormalizedAssertion], actual: &[NormalizedAssertion]) -> Option<String> {
+    if expected.len() != actual.len() {
+        return Some(format!("{} assertions expected, {} instrumented", expected.len(), actual.len()));
+    }
+    for (expected, actual) in expected.iter().zip(actual.iter()) {
+        if expected.code != actual.code {
+            return Some(format!("assertion code differs: expected `{}`, actual `{}`", expected.code, actual.code));
+        }
+        if expected.captures != actual.captures {
+            let missing: Vec<_> = expected.captures.difference(&actual.captures).collect();
+            let extra: Vec<_> = actual.captures.difference(&expected.captures).collect();
+            return Some(format!("captures of `{}` differ: missing {:?}, extra {:?}", expected.code, missing, extra));
+        }
+    }
+    None
+}
+
+#[test]
+fn conformance_with_transpiler_core() {
+    let mut unexpected_differences = Vec::new();
+    let mut resolved_differences = Vec::new();
+    for fixture_dir in transpiler_core_fixtures() {
+        let fixture_name = fixture_dir.file_name().unwrap().to_str().unwrap().to_string();
+        let cm: Lrc<SourceMap> = Default::default();
+        // keep the fixture from starting at the beginning of the SourceMap, as other tests do
+        cm.new_source_file(FileName::Anon.into(), "// conformance\n");
+
+        let expected = CaptureCollector::collect(&parse(&cm, &fixture_dir.join("expected.mjs")));
+        let mut program = parse(&cm, &fixture_dir.join("fixture.mjs"));
+        power_assert(Config::default(), cm.clone()).process(&mut program);
+        let actual = CaptureCollector::collect(&program);
+
+        let difference = describe_difference(&expected, &actual);
+        let is_known = KNOWN_DIFFERENCES.iter().any(|(name, _)| *name == fixture_name);
+        match (difference, is_known) {
+            (Some(difference), false) => unexpected_differences.push(format!("{}: {}", fixture_name, difference)),
+            (None, true) => resolved_differences.push(fixture_name),
+            _ => {}
+        }
+    }
+    assert!(unexpected_differences.is_empty(), "swc-plugin-power-assert diverges from transpiler-core:\n{}", unexpected_differences.join("\n"));
+    assert!(resolved_differences.is_empty(), "no longer differ from transpiler-core, remove them from KNOWN_DIFFERENCES: {:?}", resolved_differences);
+}
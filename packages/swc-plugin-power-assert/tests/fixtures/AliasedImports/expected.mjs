@@ -0,0 +1,17 @@
+import { strictEqual as eq, ok } from 'node:assert';
+import check from 'node:assert/strict';
+import inv from 'tiny-invariant';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(eq, null, "eq(a.b, c.d, m.e)");
+const _pasrt2 = _power_(ok, null, "ok(a.b, m.e)");
+const _pasrt3 = _power_(check, null, "check(a.b, m.e)");
+const _pasrt4 = _power_(inv, null, "inv(a.b, m.e)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt1.recorder(1);
+const _parg3 = _pasrt2.recorder(0);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt4.recorder(0);
+_pasrt1.run(_parg1.rec(_parg1.tap(a, 3, 3, 4).b, 5, 3, 6), _parg2.rec(_parg2.tap(c, 8, 8, 9).d, 10, 8, 11), m.e);
+_pasrt2.run(_parg3.rec(_parg3.tap(a, 3, 3, 4).b, 5, 3, 6), m.e);
+_pasrt3.run(_parg4.rec(_parg4.tap(a, 6, 6, 7).b, 8, 6, 9), m.e);
+_pasrt4.run(_parg5.rec(_parg5.tap(a, 4, 4, 5).b, 6, 4, 7), m.e);
@@ -0,0 +1,8 @@
+import { strictEqual as eq, ok } from 'node:assert';
+import check from 'node:assert/strict';
+import inv from 'tiny-invariant';
+
+eq(a.b, c.d, m.e);
+ok(a.b, m.e);
+check(a.b, m.e);
+inv(a.b, m.e);
@@ -0,0 +1,38 @@
+import assert from 'node:assert';
+import * as uvu from 'uvu/assert';
+import { assert as chaiAssert } from 'chai';
+import check from 'assert';
+import { assert as vtAssert } from 'vitest';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert.equal, assert, "assert.equal(actual, expected, message)");
+const _pasrt2 = _power_(uvu.equal, uvu, "uvu.equal(actual, expected, message)");
+const _pasrt3 = _power_(include, null, "include(items, item, message)");
+const _pasrt4 = _power_(assert, null, "assert(value, message)");
+const _pasrt5 = _power_(chaiAssert, null, "chaiAssert(value, message)");
+const _pasrt6 = _power_(chaiAssert.include, chaiAssert, "chaiAssert.include(items, item, message)");
+const _pasrt7 = _power_(check, null, "check(value, message)");
+const _pasrt8 = _power_(vtAssert, null, "vtAssert(value, message)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt1.recorder(1);
+const _parg3 = _pasrt2.recorder(0);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt3.recorder(1);
+const _parg6 = _pasrt3.recorder(2);
+const _parg7 = _pasrt4.recorder(0);
+const _parg8 = _pasrt5.recorder(0);
+const _parg9 = _pasrt6.recorder(0);
+const _parg10 = _pasrt6.recorder(1);
+const _parg11 = _pasrt7.recorder(0);
+const _parg12 = _pasrt7.recorder(1);
+const _parg13 = _pasrt8.recorder(0);
+function include(haystack, needle, reason) {
+    if (!haystack.includes(needle)) throw new Error(reason);
+}
+_pasrt1.run(_parg1.rec(actual, 13, 13, 19), _parg2.rec(expected, 21, 21, 29), message);
+_pasrt2.run(_parg3.rec(actual, 10, 10, 16), expected, message);
+_pasrt3.run(_parg4.rec(items, 8, 8, 13), _parg5.rec(item, 15, 15, 19), _parg6.rec(message, 21, 21, 28));
+_pasrt4.run(_parg7.rec(value, 7, 7, 12), message);
+_pasrt5.run(_parg8.rec(value, 11, 11, 16), message);
+_pasrt6.run(_parg9.rec(items, 19, 19, 24), _parg10.rec(item, 26, 26, 30), message);
+_pasrt7.run(_parg11.rec(value, 6, 6, 11), _parg12.rec(message, 13, 13, 20));
+_pasrt8.run(_parg13.rec(value, 9, 9, 14), message);
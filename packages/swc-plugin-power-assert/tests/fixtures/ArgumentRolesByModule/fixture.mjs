@@ -0,0 +1,25 @@
+import assert from 'node:assert';
+import * as uvu from 'uvu/assert';
+import { assert as chaiAssert } from 'chai';
+import check from 'assert';
+import { assert as vtAssert } from 'vitest';
+
+function include(haystack, needle, reason) {
+  if (!haystack.includes(needle)) throw new Error(reason);
+}
+
+assert.equal(actual, expected, message);
+
+uvu.equal(actual, expected, message);
+
+include(items, item, message);
+
+assert(value, message);
+
+chaiAssert(value, message);
+
+chaiAssert.include(items, item, message);
+
+check(value, message);
+
+vtAssert(value, message);
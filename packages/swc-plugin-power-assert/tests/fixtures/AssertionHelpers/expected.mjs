@@ -0,0 +1,50 @@
+import assert from 'node:assert';
+import { assertUser } from '../test-utils/assert.js';
+import checkRoles from './test-utils/assert.js';
+import { isAdmin } from './test-utils/users.js';
+import { assertMatches } from './helpers.js';
+import { _power_ } from "@power-assert/runtime";
+it('user', ()=>{
+    const _pasrt1 = _power_(assertUser, null, "assertUser(user.profile)");
+    const _pasrt2 = _power_(checkRoles, null, "checkRoles(user.roles, ['admin'])");
+    const _pasrt3 = _power_(assertMatches, null, "assertMatches(user.name, /^a/)");
+    const _pasrt4 = _power_(assertWellFormed, null, "assertWellFormed(isAdmin(user))");
+    const _pasrt5 = _power_(assertOwner, null, "assertOwner(user)");
+    const _parg1 = _pasrt1.recorder(0);
+    const _parg2 = _pasrt2.recorder(0);
+    const _parg3 = _pasrt2.recorder(1);
+    const _parg4 = _pasrt3.recorder(0);
+    const _parg5 = _pasrt3.recorder(1);
+    const _parg6 = _pasrt4.recorder(0);
+    const _parg7 = _pasrt5.recorder(0);
+    _pasrt1.run(_parg1.rec(_parg1.tap(user, 11, 11, 15).profile, 16, 11, 23));
+    _pasrt2.run(_parg2.rec(_parg2.tap(user, 11, 11, 15).roles, 16, 11, 21), _parg3.rec([
+        _parg3.tap('admin', 24, 24, 31)
+    ], 23, 23, 32));
+    _pasrt3.run(_parg4.rec(_parg4.tap(user, 14, 14, 18).name, 19, 14, 23), _parg5.rec(/^a/, 25, 25, 29));
+    _pasrt4.run(_parg6.rec(isAdmin(_parg6.tap(user, 25, 25, 29)), 17, 17, 30));
+    assertion(user.id);
+    _pasrt5.run(_parg7.rec(user, 12, 12, 16));
+    assertResult(user);
+});
+it('shadowed', ()=>{
+    const assertWellFormed = makeChecker();
+    assertWellFormed(user);
+});
+const assertOwner = (u)=>{
+    const _pasrt6 = _power_(assert, null, "assert(u.owner === user)", {
+        binexp: "==="
+    });
+    const _parg8 = _pasrt6.recorder(0);
+    _pasrt6.run(_parg8.rec(_parg8.tap(_parg8.tap(u, 7, 7, 8).owner, 9, 7, 14, {
+        hint: "left"
+    }) === _parg8.tap(user, 19, 19, 23, {
+        hint: "right"
+    }), 15, 7, 23));
+};
+const assertResult = compute(user);
+function assertWellFormed(u) {
+    const _pasrt7 = _power_(assert, null, "assert(u.name.length > 0)");
+    const _parg9 = _pasrt7.recorder(0);
+    _pasrt7.run(_parg9.rec(_parg9.tap(_parg9.tap(_parg9.tap(u, 7, 7, 8).name, 9, 7, 13).length, 14, 7, 20) > _parg9.tap(0, 23, 23, 24), 21, 7, 24));
+}
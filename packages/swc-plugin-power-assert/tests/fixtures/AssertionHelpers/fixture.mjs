@@ -0,0 +1,29 @@
+import assert from 'node:assert';
+import { assertUser } from '../test-utils/assert.js';
+import checkRoles from './test-utils/assert.js';
+import { isAdmin } from './test-utils/users.js';
+import { assertMatches } from './helpers.js';
+
+it('user', () => {
+  assertUser(user.profile);
+  checkRoles(user.roles, ['admin']);
+  assertMatches(user.name, /^a/);
+  assertWellFormed(isAdmin(user));
+  assertion(user.id);
+  assertOwner(user);
+  assertResult(user);
+});
+
+it('shadowed', () => {
+  const assertWellFormed = makeChecker();
+  assertWellFormed(user);
+});
+
+const assertOwner = (u) => {
+  assert(u.owner === user);
+};
+const assertResult = compute(user);
+
+function assertWellFormed (u) {
+  assert(u.name.length > 0);
+}
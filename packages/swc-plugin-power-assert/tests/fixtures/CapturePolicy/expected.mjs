@@ -0,0 +1,39 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert, null, "assert(truthy === 'foo')", {
+    binexp: "==="
+});
+const _pasrt2 = _power_(assert, null, "assert(this.items.includes(2))");
+const _pasrt3 = _power_(assert.equal, assert, "assert.equal(ary.indexOf(zero), 2)");
+const _pasrt4 = _power_(assert.deepEqual, assert, "assert.deepEqual(obj.bar, [1, 'two', /re/, null, true])");
+const _pasrt5 = _power_(assert, null, "assert(`${name}` !== 'bob')", {
+    binexp: "!=="
+});
+const _pasrt6 = _power_(assert, null, "assert(this.count > 0 && flag === undefined)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt3.recorder(0);
+const _parg4 = _pasrt4.recorder(0);
+const _parg5 = _pasrt4.recorder(1);
+const _parg6 = _pasrt5.recorder(0);
+const _parg7 = _pasrt6.recorder(0);
+_pasrt1.run(_parg1.rec(_parg1.tap(truthy, 7, 7, 13, {
+    hint: "left"
+}) === _parg1.tap('foo', 18, 18, 23, {
+    hint: "right"
+}), 14, 7, 23));
+_pasrt2.run(_parg2.rec(_parg2.tap(this.items, 12, 7, 17).includes(2), 18, 7, 29));
+_pasrt3.run(_parg3.rec(_parg3.tap(ary, 13, 13, 16).indexOf(_parg3.tap(zero, 25, 25, 29)), 17, 13, 30), 2);
+_pasrt4.run(_parg4.rec(_parg4.tap(obj, 17, 17, 20).bar, 21, 17, 24), _parg5.rec([
+    1,
+    'two',
+    /re/,
+    null,
+    true
+], 26, 26, 54));
+_pasrt5.run(_parg6.rec(_parg6.tap(`${_parg6.tap(name, 10, 10, 14)}`, 7, 7, 16, {
+    hint: "left"
+}) !== _parg6.tap('bob', 21, 21, 26, {
+    hint: "right"
+}), 17, 7, 26));
+_pasrt6.run(_parg7.rec(_parg7.tap(_parg7.tap(this.count, 12, 7, 17) > 0, 18, 7, 21) && _parg7.tap(_parg7.tap(flag, 25, 25, 29) === _parg7.tap(undefined, 34, 34, 43), 30, 25, 43), 22, 7, 43));
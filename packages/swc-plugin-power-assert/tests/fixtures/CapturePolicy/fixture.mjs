@@ -0,0 +1,13 @@
+import assert from 'node:assert';
+
+assert(truthy === 'foo');
+
+assert(this.items.includes(2));
+
+assert.equal(ary.indexOf(zero), 2);
+
+assert.deepEqual(obj.bar, [1, 'two', /re/, null, true]);
+
+assert(`${name}` !== 'bob');
+
+assert(this.count > 0 && flag === undefined);
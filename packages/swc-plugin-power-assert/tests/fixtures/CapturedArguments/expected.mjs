@@ -0,0 +1,29 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert.ok, assert, "assert.ok(truthy, 'should be ok')");
+const _pasrt2 = _power_(assert.equal, assert, "assert.equal(foo.bar, baz, `${name} should be baz`)");
+const _pasrt3 = _power_(assert.throws, assert, "assert.throws(() => fn(arg), TypeError, 'should throw')");
+const _pasrt4 = _power_(assert.match, assert, "assert.match(str, /^foo/, message)");
+const _pasrt5 = _power_(assert.fail, assert, "assert.fail(reason)");
+const _pasrt6 = _power_(assert.rejects, assert, "assert.rejects(promise, SyntaxError)");
+const _pasrt7 = _power_(assert.custom, assert, "assert.custom(foo, bar)");
+const _pasrt8 = _power_(assert.equal, assert, "assert.equal(...args, 'message')");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.recorder(1);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt4.recorder(0);
+const _parg6 = _pasrt4.recorder(1);
+const _parg7 = _pasrt6.recorder(0);
+const _parg8 = _pasrt6.recorder(1);
+const _parg9 = _pasrt7.recorder(1);
+const _parg10 = _pasrt8.spreadRecorder(0);
+const _parg11 = _pasrt8.recorder(1);
+_pasrt1.run(_parg1.rec(truthy, 10, 10, 16), 'should be ok');
+_pasrt2.run(_parg2.rec(_parg2.tap(foo, 13, 13, 16).bar, 17, 13, 20), _parg3.rec(baz, 22, 22, 25), `${name} should be baz`);
+_pasrt3.run(_parg4.rec(()=>_parg4.tap(fn(_parg4.tap(arg, 23, 23, 26)), 20, 20, 27), 14, 14, 27), TypeError, 'should throw');
+_pasrt4.run(_parg5.rec(str, 13, 13, 16), _parg6.rec(/^foo/, 18, 18, 24), message);
+assert.fail(reason);
+await _pasrt6.run(_parg7.rec(promise, 15, 15, 22), _parg8.rec(SyntaxError, 24, 24, 35));
+_pasrt7.run(foo, _parg9.rec(bar, 19, 19, 22));
+_pasrt8.run(_parg10.rec(args, 16, 16, 20), _parg11.rec('message', 22, 22, 31));
@@ -0,0 +1,17 @@
+import assert from 'node:assert';
+
+assert.ok(truthy, 'should be ok');
+
+assert.equal(foo.bar, baz, `${name} should be baz`);
+
+assert.throws(() => fn(arg), TypeError, 'should throw');
+
+assert.match(str, /^foo/, message);
+
+assert.fail(reason);
+
+await assert.rejects(promise, SyntaxError);
+
+assert.custom(foo, bar);
+
+assert.equal(...args, 'message');
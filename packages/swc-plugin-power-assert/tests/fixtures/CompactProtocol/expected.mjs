@@ -0,0 +1,90 @@
+import assert from 'node:assert';
+import { _power_table_ } from "@power-assert/runtime";
+const _patbl = _power_table_([
+    [
+        "assert.equal(`${たろう}はやまだ`, 'やまだ🍣')",
+        [
+            13,
+            13,
+            25,
+            16,
+            16,
+            19,
+            27,
+            27,
+            34
+        ]
+    ],
+    [
+        "assert(true)",
+        [
+            7,
+            7,
+            11
+        ]
+    ],
+    [
+        "assert(truthy === falsy)",
+        [
+            14,
+            7,
+            23,
+            7,
+            7,
+            13,
+            18,
+            18,
+            23
+        ],
+        {
+            binexp: "==="
+        }
+    ],
+    [
+        "assert.deepEqual(foo.bar, [baz, 1])",
+        [
+            21,
+            17,
+            24,
+            17,
+            17,
+            20,
+            26,
+            26,
+            34,
+            27,
+            27,
+            30,
+            32,
+            32,
+            33
+        ]
+    ]
+]);
+const _pasrt3 = _patbl(2, assert, null);
+const _pasrt4 = _patbl(3, assert.deepEqual, assert);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt4.recorder(0);
+const _parg6 = _pasrt4.recorder(1);
+describe('compact protocol', ()=>{
+    it('multibyte', ()=>{
+        const _pasrt1 = _patbl(0, assert.equal, assert);
+        const _parg1 = _pasrt1.recorder(0);
+        const _parg2 = _pasrt1.recorder(1);
+        _pasrt1.run(_parg1.rec(`${_parg1.tap(たろう, 1)}はやまだ`, 0), _parg2.rec('やまだ🍣', 2));
+    });
+    it('no captures', ()=>{
+        const _pasrt2 = _patbl(1, assert, null);
+        const _parg3 = _pasrt2.recorder(0);
+        _pasrt2.run(_parg3.rec(true, 0));
+    });
+});
+_pasrt3.run(_parg4.rec(_parg4.tap(truthy, 1, {
+    hint: "left"
+}) === _parg4.tap(falsy, 2, {
+    hint: "right"
+}), 0));
+_pasrt4.run(_parg5.rec(_parg5.tap(foo, 1).bar, 0), _parg6.rec([
+    _parg6.tap(baz, 3),
+    _parg6.tap(1, 4)
+], 2));
@@ -0,0 +1,13 @@
+import assert from 'node:assert';
+
+describe('compact protocol', () => {
+  it('multibyte', () => {
+    assert.equal(`${たろう}はやまだ`, 'やまだ🍣');
+  });
+  it('no captures', () => {
+    assert(true);
+  });
+});
+
+assert(truthy === falsy);
+assert.deepEqual(foo.bar, [baz, 1]);
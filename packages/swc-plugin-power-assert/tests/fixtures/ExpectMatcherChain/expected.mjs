@@ -0,0 +1,28 @@
+import { describe, it, expect } from 'vitest';
+import { _power_ } from "@power-assert/runtime";
+describe('matcher chains', ()=>{
+    it('toBe', ()=>{
+        const _pasrt1 = _power_(expect, null, "expect(user.roles.includes('admin')).toBe(true)");
+        const _parg1 = _pasrt1.recorder(0);
+        const _parg2 = _pasrt1.recorder(1);
+        _pasrt1.runChain((_paexpect, _paval0, _paval1)=>_paexpect(_paval0).toBe(_paval1), _parg1.rec(_parg1.tap(_parg1.tap(user, 7, 7, 11).roles, 12, 7, 17).includes(_parg1.tap('admin', 27, 27, 34)), 18, 7, 35), _parg2.rec(true, 42, 42, 46));
+    });
+    it('not', ()=>{
+        const _pasrt2 = _power_(expect, null, "expect(items.length).not.toEqual(expected.length)");
+        const _parg3 = _pasrt2.recorder(0);
+        const _parg4 = _pasrt2.recorder(1);
+        _pasrt2.runChain((_paexpect, _paval0, _paval1)=>_paexpect(_paval0).not.toEqual(_paval1), _parg3.rec(_parg3.tap(items, 7, 7, 12).length, 13, 7, 19), _parg4.rec(_parg4.tap(expected, 33, 33, 41).length, 42, 33, 48));
+    });
+    it('resolves', async ()=>{
+        const _pasrt3 = _power_(expect, null, "expect(fetchUser(id)).resolves.toMatchObject({ name })");
+        const _parg5 = _pasrt3.recorder(0);
+        const _parg6 = _pasrt3.recorder(1);
+        await _pasrt3.runChain((_paexpect, _paval0, _paval1)=>_paexpect(_paval0).resolves.toMatchObject(_paval1), _parg5.rec(fetchUser(_parg5.tap(id, 17, 17, 19)), 7, 7, 20), _parg6.rec({
+            name
+        }, 45, 45, 53));
+    });
+    it('not a matcher chain', ()=>{
+        const matchers = expect(actual);
+        expect.assertions(1);
+    });
+});
@@ -0,0 +1,17 @@
+import { describe, it, expect } from 'vitest';
+
+describe('matcher chains', () => {
+  it('toBe', () => {
+    expect(user.roles.includes('admin')).toBe(true);
+  });
+  it('not', () => {
+    expect(items.length).not.toEqual(expected.length);
+  });
+  it('resolves', async () => {
+    await expect(fetchUser(id)).resolves.toMatchObject({ name });
+  });
+  it('not a matcher chain', () => {
+    const matchers = expect(actual);
+    expect.assertions(1);
+  });
+});
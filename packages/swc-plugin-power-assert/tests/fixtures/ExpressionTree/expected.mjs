@@ -0,0 +1,75 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert, null, "assert(ary.indexOf(zero) === two)", {
+    binexp: "==="
+});
+const _pasrt2 = _power_(assert.ok, assert, "assert.ok(foo?.bar && !baz)");
+const _pasrt3 = _power_(assert.deepEqual, assert, "assert.deepEqual(new Foo(x), [y, 1])");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt3.recorder(0);
+const _parg4 = _pasrt3.recorder(1);
+_pasrt1.run(_parg1.rec(_parg1.tap(_parg1.tap(ary, 7, 7, 10, {
+    id: 2,
+    kind: "Identifier",
+    parent: 1
+}).indexOf(_parg1.tap(zero, 19, 19, 23, {
+    id: 3,
+    kind: "Identifier",
+    parent: 1
+})), 11, 7, 24, {
+    id: 1,
+    kind: "CallExpression",
+    parent: 0,
+    hint: "left"
+}) === _parg1.tap(two, 29, 29, 32, {
+    id: 4,
+    kind: "Identifier",
+    parent: 0,
+    hint: "right"
+}), 25, 7, 32, {
+    id: 0,
+    kind: "BinaryExpression"
+}));
+_pasrt2.run(_parg2.rec(_parg2.tap(_parg2.tap(foo, 10, 10, 13, {
+    id: 2,
+    kind: "Identifier",
+    parent: 1
+})?.bar, 10, 10, 18, {
+    id: 1,
+    kind: "MemberExpression",
+    parent: 0
+}) && _parg2.tap(!_parg2.tap(baz, 23, 23, 26, {
+    id: 4,
+    kind: "Identifier",
+    parent: 3
+}), 22, 22, 26, {
+    id: 3,
+    kind: "UnaryExpression",
+    parent: 0
+}), 19, 10, 26, {
+    id: 0,
+    kind: "LogicalExpression"
+}));
+_pasrt3.run(_parg3.rec(new Foo(_parg3.tap(x, 25, 25, 26, {
+    id: 1,
+    kind: "Identifier",
+    parent: 0
+})), 17, 17, 27, {
+    id: 0,
+    kind: "NewExpression"
+}), _parg4.rec([
+    _parg4.tap(y, 30, 30, 31, {
+        id: 3,
+        kind: "Identifier",
+        parent: 2
+    }),
+    _parg4.tap(1, 33, 33, 34, {
+        id: 4,
+        kind: "Literal",
+        parent: 2
+    })
+], 29, 29, 35, {
+    id: 2,
+    kind: "ArrayExpression"
+}));
@@ -0,0 +1,7 @@
+import assert from 'node:assert';
+
+assert(ary.indexOf(zero) === two);
+
+assert.ok(foo?.bar && !baz);
+
+assert.deepEqual(new Foo(x), [y, 1]);
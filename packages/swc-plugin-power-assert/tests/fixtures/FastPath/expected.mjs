@@ -0,0 +1,41 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert, null, "assert(truthy === falsy)", {
+    binexp: "==="
+});
+const _pasrt2 = _power_(assert.deepEqual, assert, "assert.deepEqual(foo.bar, [baz, { qux: `${quux}` }])");
+const _pasrt3 = _power_(assert.equal, assert, "assert.equal(ary.indexOf(zero), two)");
+const _pasrt4 = _power_(assert.ok, assert, "assert.ok(counter++ > 0)");
+const _pasrt5 = _power_(assert, null, "assert(new Foo(x))");
+const _pasrt6 = _power_(assert.rejects, assert, "assert.rejects(promise)");
+const _pasrt7 = _power_(assert.ok, assert, "assert.ok(...args)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.recorder(1);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt3.recorder(1);
+const _parg6 = _pasrt4.recorder(0);
+const _parg7 = _pasrt5.recorder(0);
+const _parg8 = _pasrt6.recorder(0);
+const _parg9 = _pasrt7.spreadRecorder(0);
+_pasrt1.tryRun(()=>assert(truthy === falsy), ()=>_pasrt1.run(_parg1.rec(_parg1.tap(truthy, 7, 7, 13, {
+        hint: "left"
+    }) === _parg1.tap(falsy, 18, 18, 23, {
+        hint: "right"
+    }), 14, 7, 23)));
+_pasrt2.tryRun(()=>assert.deepEqual(foo.bar, [
+        baz,
+        {
+            qux: `${quux}`
+        }
+    ]), ()=>_pasrt2.run(_parg2.rec(_parg2.tap(foo, 17, 17, 20).bar, 21, 17, 24), _parg3.rec([
+        _parg3.tap(baz, 27, 27, 30),
+        _parg3.tap({
+            qux: _parg3.tap(`${_parg3.tap(quux, 42, 42, 46)}`, 39, 39, 48)
+        }, 32, 32, 50)
+    ], 26, 26, 51)));
+_pasrt3.run(_parg4.rec(_parg4.tap(ary, 13, 13, 16).indexOf(_parg4.tap(zero, 25, 25, 29)), 17, 13, 30), _parg5.rec(two, 32, 32, 35));
+_pasrt4.run(_parg6.rec(_parg6.tap(counter++, 17, 10, 19) > _parg6.tap(0, 22, 22, 23), 20, 10, 23));
+_pasrt5.run(_parg7.rec(new Foo(_parg7.tap(x, 15, 15, 16)), 7, 7, 17));
+await _pasrt6.run(_parg8.rec(promise, 15, 15, 22));
+_pasrt7.run(_parg9.rec(args, 13, 13, 17));
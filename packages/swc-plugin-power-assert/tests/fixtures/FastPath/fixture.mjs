@@ -0,0 +1,15 @@
+import assert from 'node:assert';
+
+assert(truthy === falsy);
+
+assert.deepEqual(foo.bar, [baz, { qux: `${quux}` }]);
+
+assert.equal(ary.indexOf(zero), two);
+
+assert.ok(counter++ > 0);
+
+assert(new Foo(x));
+
+await assert.rejects(promise);
+
+assert.ok(...args);
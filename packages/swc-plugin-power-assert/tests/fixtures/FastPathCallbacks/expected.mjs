@@ -0,0 +1,29 @@
+import assert from 'node:assert';
+import * as uvu from 'uvu/assert';
+import { assertThrows, assertRejects } from '@std/assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert.throws, assert, "assert.throws(parseInput)");
+const _pasrt2 = _power_(assert.doesNotThrow, assert, "assert.doesNotThrow(validate)");
+const _pasrt3 = _power_(assert.rejects, assert, "assert.rejects(load)");
+const _pasrt4 = _power_(assert.doesNotReject, assert, "assert.doesNotReject(promise)");
+const _pasrt5 = _power_(uvu.throws, uvu, "uvu.throws(parseInput)");
+const _pasrt6 = _power_(assertThrows, null, "assertThrows(parseInput)");
+const _pasrt7 = _power_(assertRejects, null, "assertRejects(load)");
+const _pasrt8 = _power_(assert.equal, assert, "assert.equal(result, expected)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt3.recorder(0);
+const _parg4 = _pasrt4.recorder(0);
+const _parg5 = _pasrt5.recorder(0);
+const _parg6 = _pasrt6.recorder(0);
+const _parg7 = _pasrt7.recorder(0);
+const _parg8 = _pasrt8.recorder(0);
+const _parg9 = _pasrt8.recorder(1);
+_pasrt1.run(_parg1.rec(parseInput, 14, 14, 24));
+_pasrt2.run(_parg2.rec(validate, 20, 20, 28));
+await _pasrt3.run(_parg3.rec(load, 15, 15, 19));
+await _pasrt4.run(_parg4.rec(promise, 21, 21, 28));
+_pasrt5.run(_parg5.rec(parseInput, 11, 11, 21));
+_pasrt6.run(_parg6.rec(parseInput, 13, 13, 23));
+await _pasrt7.run(_parg7.rec(load, 14, 14, 18));
+_pasrt8.tryRun(()=>assert.equal(result, expected), ()=>_pasrt8.run(_parg8.rec(result, 13, 13, 19), _parg9.rec(expected, 21, 21, 29)));
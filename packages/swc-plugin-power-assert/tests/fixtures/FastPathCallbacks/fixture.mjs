@@ -0,0 +1,19 @@
+import assert from 'node:assert';
+import * as uvu from 'uvu/assert';
+import { assertThrows, assertRejects } from '@std/assert';
+
+assert.throws(parseInput);
+
+assert.doesNotThrow(validate);
+
+await assert.rejects(load);
+
+await assert.doesNotReject(promise);
+
+uvu.throws(parseInput);
+
+assertThrows(parseInput);
+
+await assertRejects(load);
+
+assert.equal(result, expected);
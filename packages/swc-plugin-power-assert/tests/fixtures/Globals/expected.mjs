@@ -0,0 +1,13 @@
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(console.assert, console, "console.assert(items.length > 0, 'no items')");
+const _pasrt2 = _power_(assert.equal, assert, "assert.equal(foo.bar, baz)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.recorder(1);
+_pasrt1.run(_parg1.rec(_parg1.tap(_parg1.tap(items, 15, 15, 20).length, 21, 15, 27) > _parg1.tap(0, 30, 30, 31), 28, 15, 31), 'no items');
+_pasrt2.run(_parg2.rec(_parg2.tap(foo, 13, 13, 16).bar, 17, 13, 20), _parg3.rec(baz, 22, 22, 25));
+console.log(items.length > 0);
+function shadowed(assert1, console1) {
+    assert1(truthy);
+    console1.assert(truthy);
+}
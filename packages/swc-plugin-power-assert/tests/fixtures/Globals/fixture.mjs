@@ -0,0 +1,8 @@
+console.assert(items.length > 0, 'no items');
+assert.equal(foo.bar, baz);
+console.log(items.length > 0);
+
+function shadowed (assert, console) {
+  assert(truthy);
+  console.assert(truthy);
+}
@@ -0,0 +1,11 @@
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(console.assert, console, "console.assert(items.length > 0, 'no items')");
+const _pasrt2 = _power_(assert, null, "assert(items.length > 0)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+_pasrt1.run(_parg1.rec(_parg1.tap(_parg1.tap(items, 15, 15, 20).length, 21, 15, 27) > _parg1.tap(0, 30, 30, 31), 28, 15, 31), 'no items');
+_pasrt2.tryRun(()=>assert(items.length > 0), ()=>_pasrt2.run(_parg2.rec(_parg2.tap(_parg2.tap(items, 7, 7, 12).length, 13, 7, 19) > _parg2.tap(0, 22, 22, 23), 20, 7, 23)));
+console.assert.call(console, items.length > 0);
+assert.apply(null, [
+    items.length > 0
+]);
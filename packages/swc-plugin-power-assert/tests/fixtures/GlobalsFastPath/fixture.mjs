@@ -0,0 +1,4 @@
+console.assert(items.length > 0, 'no items');
+assert(items.length > 0);
+console.assert.call(console, items.length > 0);
+assert.apply(null, [items.length > 0]);
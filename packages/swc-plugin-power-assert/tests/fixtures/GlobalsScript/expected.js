@@ -0,0 +1,2 @@
+console.assert(items.length > 0, 'no items');
+assert.equal(foo.bar, baz);
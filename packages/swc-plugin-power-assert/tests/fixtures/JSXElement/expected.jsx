@@ -0,0 +1,6 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert.deepEqual, assert, "assert.deepEqual(render(<Greeting name={user.name} />), <p>Hello</p>)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt1.recorder(1);
+_pasrt1.run(_parg1.rec(render(_parg1.tap(<Greeting name={_parg1.tap(_parg1.tap(user, 40, 40, 44).name, 45, 40, 49)}/>, 24, 24, 53)), 17, 17, 54), _parg2.rec(<p>Hello</p>, 56, 56, 68));
@@ -0,0 +1,3 @@
+import assert from 'node:assert';
+
+assert.deepEqual(render(<Greeting name={user.name} />), <p>Hello</p>);
@@ -0,0 +1,31 @@
+import * as vt from 'vitest';
+import vitest from 'vitest';
+import { _power_ } from "@power-assert/runtime";
+vt.describe('namespace import', ()=>{
+    vt.it('only allowlisted members are instrumented', ()=>{
+        const _pasrt1 = _power_(vt.assert, vt, "vt.assert(truthy === falsy)", {
+            binexp: "==="
+        });
+        const _pasrt2 = _power_(vt.assert.equal, vt.assert, "vt.assert.equal(truthy, falsy)");
+        const _pasrt3 = _power_(vt.assert.deepEqual, vt.assert, "vt.assert.deepEqual(foo.bar, [baz])");
+        const _pasrt4 = _power_(vitest.assert.isOk, vitest.assert, "vitest.assert.isOk(truthy)");
+        const _parg1 = _pasrt1.recorder(0);
+        const _parg2 = _pasrt2.recorder(0);
+        const _parg3 = _pasrt2.recorder(1);
+        const _parg4 = _pasrt3.recorder(0);
+        const _parg5 = _pasrt3.recorder(1);
+        const _parg6 = _pasrt4.recorder(0);
+        vt.expect(truthy).toBe(falsy);
+        _pasrt1.run(_parg1.rec(_parg1.tap(truthy, 10, 10, 16, {
+            hint: "left"
+        }) === _parg1.tap(falsy, 21, 21, 26, {
+            hint: "right"
+        }), 17, 10, 26));
+        _pasrt2.run(_parg2.rec(truthy, 16, 16, 22), _parg3.rec(falsy, 24, 24, 29));
+        _pasrt3.run(_parg4.rec(_parg4.tap(foo, 20, 20, 23).bar, 24, 20, 27), _parg5.rec([
+            _parg5.tap(baz, 30, 30, 33)
+        ], 29, 29, 34));
+        _pasrt4.run(_parg6.rec(truthy, 19, 19, 25));
+        vitest.expect(truthy).toBeTruthy();
+    });
+});
@@ -0,0 +1,13 @@
+import * as vt from 'vitest';
+import vitest from 'vitest';
+
+vt.describe('namespace import', () => {
+  vt.it('only allowlisted members are instrumented', () => {
+    vt.expect(truthy).toBe(falsy);
+    vt.assert(truthy === falsy);
+    vt.assert.equal(truthy, falsy);
+    vt.assert.deepEqual(foo.bar, [baz]);
+    vitest.assert.isOk(truthy);
+    vitest.expect(truthy).toBeTruthy();
+  });
+});
@@ -0,0 +1,35 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert.doesNotThrow, assert, "assert.doesNotThrow(function () {\n  assert(x > 1);\n})");
+const _pasrt3 = _power_(assert.rejects, assert, "assert.rejects(async function () {\n  assert.equal(a, b);\n})");
+const _pasrt5 = _power_(assert.doesNotThrow, assert, "assert.doesNotThrow(() => {\n  assert.ok(items.includes(item));\n})");
+const _pasrt8 = _power_(assert, null, "assert(foo.bar)");
+const _pasrt7 = _power_(assert.throws, assert, "assert.throws(() => assert(foo.bar), TypeError)");
+const _pasrt9 = _power_(assert, null, "assert(truthy)");
+const _parg6 = _pasrt5.recorder(0);
+const _parg9 = _pasrt8.recorder(0);
+const _parg8 = _pasrt7.recorder(0);
+const _parg10 = _pasrt9.recorder(0);
+assert.doesNotThrow(function() {
+    const _pasrt2 = _power_(assert, null, "assert(x > 1)");
+    const _parg2 = _pasrt2.recorder(0);
+    _pasrt2.run(_parg2.rec(_parg2.tap(x, 7, 7, 8) > _parg2.tap(1, 11, 11, 12), 9, 7, 12));
+});
+await assert.rejects(async function() {
+    const _pasrt4 = _power_(assert.equal, assert, "assert.equal(a, b)");
+    const _parg4 = _pasrt4.recorder(0);
+    const _parg5 = _pasrt4.recorder(1);
+    _pasrt4.run(_parg4.rec(a, 13, 13, 14), _parg5.rec(b, 16, 16, 17));
+});
+_pasrt5.run(_parg6.rec(()=>{
+    const _pasrt6 = _power_(assert.ok, assert, "assert.ok(items.includes(item))");
+    const _parg7 = _pasrt6.recorder(0);
+    _pasrt6.run(_parg7.rec(_parg7.tap(items, 10, 10, 15).includes(_parg7.tap(item, 25, 25, 29)), 16, 10, 30));
+}, 20, 20, 64));
+_pasrt7.run(_parg8.rec(()=>_parg8.tap(_pasrt8.run(_parg9.rec(_parg9.tap(foo, 7, 7, 10).bar, 11, 7, 14)), 20, 20, 35), 14, 14, 35), TypeError);
+_pasrt9.run(_parg10.rec(truthy, 7, 7, 13));
+{
+    const _pasrt10 = _power_(assert.ok, assert, "assert.ok(obj.flag)");
+    const _parg11 = _pasrt10.recorder(0);
+    _pasrt10.run(_parg11.rec(_parg11.tap(obj, 10, 10, 13).flag, 14, 10, 18));
+}
@@ -0,0 +1,21 @@
+import assert from 'node:assert';
+
+assert.doesNotThrow(function () {
+  assert(x > 1);
+});
+
+await assert.rejects(async function () {
+  assert.equal(a, b);
+});
+
+assert.doesNotThrow(() => {
+  assert.ok(items.includes(item));
+});
+
+assert.throws(() => assert(foo.bar), TypeError);
+
+assert(truthy);
+
+{
+  assert.ok(obj.flag);
+}
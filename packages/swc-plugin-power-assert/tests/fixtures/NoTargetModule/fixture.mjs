@@ -0,0 +1,7 @@
+import { assert } from 'chai';
+import { strict } from './assert.mjs';
+
+function check (value) {
+  assert(value === 1);
+  strict.equal(value, 1);
+}
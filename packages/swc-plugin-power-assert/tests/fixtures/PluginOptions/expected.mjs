@@ -0,0 +1,12 @@
+import ok from 'my-assert';
+import { assert, expect } from 'vitest';
+import nodeAssert from 'node:assert';
+import { _power_ } from "./power-assert-runtime.mjs";
+const _pasrt1 = _power_(ok, null, "ok(truthy)");
+const _pasrt2 = _power_(expect, null, "expect(truthy)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+_pasrt1.run(_parg1.rec(truthy, 3, 3, 9));
+_pasrt2.run(_parg2.rec(truthy, 7, 7, 13));
+assert(truthy);
+nodeAssert(truthy);
@@ -0,0 +1,8 @@
+import ok from 'my-assert';
+import { assert, expect } from 'vitest';
+import nodeAssert from 'node:assert';
+
+ok(truthy);
+expect(truthy);
+assert(truthy);
+nodeAssert(truthy);
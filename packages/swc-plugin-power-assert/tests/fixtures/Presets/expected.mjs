@@ -0,0 +1,32 @@
+import { assert as chaiAssert } from 'chai';
+import * as uvu from 'uvu/assert';
+import { assertEquals, assertThrows } from 'jsr:@std/assert';
+import { expect } from 'bun:test';
+import assert from 'power-assert';
+import invariant from 'tiny-invariant';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(chaiAssert.isTrue, chaiAssert, "chaiAssert.isTrue(user.isAdmin, 'should be admin')");
+const _pasrt2 = _power_(uvu.is, uvu, "uvu.is(foo.bar, baz, 'should be baz')");
+const _pasrt3 = _power_(assertEquals, null, "assertEquals(items.length, 3, 'three items')");
+const _pasrt4 = _power_(assertThrows, null, "assertThrows(() => parse(input), SyntaxError, 'Unexpected token')");
+const _pasrt5 = _power_(expect, null, "expect(user.name).toBe(name)");
+const _pasrt6 = _power_(assert.equal, assert, "assert.equal(actual.value, expected, 'message')");
+const _pasrt7 = _power_(invariant, null, "invariant(config.enabled, 'config should be enabled')");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.recorder(1);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt3.recorder(1);
+const _parg6 = _pasrt4.recorder(0);
+const _parg7 = _pasrt5.recorder(0);
+const _parg8 = _pasrt5.recorder(1);
+const _parg9 = _pasrt6.recorder(0);
+const _parg10 = _pasrt6.recorder(1);
+const _parg11 = _pasrt7.recorder(0);
+_pasrt1.run(_parg1.rec(_parg1.tap(user, 18, 18, 22).isAdmin, 23, 18, 30), 'should be admin');
+_pasrt2.run(_parg2.rec(_parg2.tap(foo, 7, 7, 10).bar, 11, 7, 14), _parg3.rec(baz, 16, 16, 19), 'should be baz');
+_pasrt3.run(_parg4.rec(_parg4.tap(items, 13, 13, 18).length, 19, 13, 25), _parg5.rec(3, 27, 27, 28), 'three items');
+_pasrt4.run(_parg6.rec(()=>_parg6.tap(parse(_parg6.tap(input, 25, 25, 30)), 19, 19, 31), 13, 13, 31), SyntaxError, 'Unexpected token');
+_pasrt5.runChain((_paexpect, _paval0, _paval1)=>_paexpect(_paval0).toBe(_paval1), _parg7.rec(_parg7.tap(user, 7, 7, 11).name, 12, 7, 16), _parg8.rec(name, 23, 23, 27));
+_pasrt6.run(_parg9.rec(_parg9.tap(actual, 13, 13, 19).value, 20, 13, 25), _parg10.rec(expected, 27, 27, 35), 'message');
+_pasrt7.run(_parg11.rec(_parg11.tap(config, 10, 10, 16).enabled, 17, 10, 24), 'config should be enabled');
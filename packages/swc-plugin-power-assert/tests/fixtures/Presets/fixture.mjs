@@ -0,0 +1,20 @@
+import { assert as chaiAssert } from 'chai';
+import * as uvu from 'uvu/assert';
+import { assertEquals, assertThrows } from 'jsr:@std/assert';
+import { expect } from 'bun:test';
+import assert from 'power-assert';
+import invariant from 'tiny-invariant';
+
+chaiAssert.isTrue(user.isAdmin, 'should be admin');
+
+uvu.is(foo.bar, baz, 'should be baz');
+
+assertEquals(items.length, 3, 'three items');
+
+assertThrows(() => parse(input), SyntaxError, 'Unexpected token');
+
+expect(user.name).toBe(name);
+
+assert.equal(actual.value, expected, 'message');
+
+invariant(config.enabled, 'config should be enabled');
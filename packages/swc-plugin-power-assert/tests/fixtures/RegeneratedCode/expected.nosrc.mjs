@@ -0,0 +1,30 @@
+import assert from 'node:assert/strict';
+import { _power_ } from "@power-assert/runtime";
+{
+    const _pasrt1 = _power_(assert, null, "assert(truthy)");
+    const _parg1 = _pasrt1.recorder(0);
+    _pasrt1.run(_parg1.rec(truthy, 7, 7, 13));
+}{
+    const _pasrt2 = _power_(assert.equal, assert, "assert.equal(foo.bar, 'baz')");
+    const _parg2 = _pasrt2.recorder(0);
+    const _parg3 = _pasrt2.recorder(1);
+    _pasrt2.run(_parg2.rec(_parg2.tap(foo, 13, 13, 16).bar /* comment */ , 17, 13, 20), _parg3.rec('baz', 22, 22, 27));
+}{
+    const _pasrt3 = _power_(assert, null, "assert(a.b === c[d])", {
+        binexp: "==="
+    });
+    const _parg4 = _pasrt3.recorder(0);
+    _pasrt3.run(_parg4.rec(_parg4.tap(_parg4.tap(a, 7, 7, 8).b, 9, 7, 10, {
+        hint: "left"
+    }) === _parg4.tap(_parg4.tap(c, 15, 15, 16)[_parg4.tap(d, 17, 17, 18)], 16, 15, 19, {
+        hint: "right"
+    }), 11, 7, 19));
+}{
+    const _pasrt4 = _power_(assert, null, "assert(x ? y.z : w())");
+    const _parg5 = _pasrt4.recorder(0);
+    _pasrt4.run(_parg5.rec(_parg5.tap(x, 7, 7, 8) ? _parg5.tap(_parg5.tap(y, 11, 11, 12).z, 13, 11, 14) : _parg5.tap(w(), 17, 17, 20), 9, 7, 20));
+}{
+    const _pasrt5 = _power_(assert, null, "assert(obj.items.includes('ほげ'), 'メッセージ')");
+    const _parg6 = _pasrt5.recorder(0);
+    _pasrt5.run(_parg6.rec(_parg6.tap(_parg6.tap(obj, 7, 7, 10).items, 11, 7, 16).includes(_parg6.tap('ほげ', 26, 26, 30)), 17, 7, 31), 'メッセージ');
+}
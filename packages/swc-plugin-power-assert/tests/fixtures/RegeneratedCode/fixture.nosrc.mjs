@@ -0,0 +1,19 @@
+import assert from 'node:assert/strict';
+
+{
+  assert(  truthy  );
+}
+{
+  assert.equal(foo.bar /* comment */ , 'baz');
+}
+{
+  assert(a.b   ===  c[d]);
+}
+{
+  assert(x
+    ? y.z
+    : w());
+}
+{
+  assert(obj.items.includes('ほげ'), 'メッセージ');
+}
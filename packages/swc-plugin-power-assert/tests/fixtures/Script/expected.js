@@ -0,0 +1,3 @@
+'use strict';
+const assert = require('node:assert');
+assert(user.name === 'alice');
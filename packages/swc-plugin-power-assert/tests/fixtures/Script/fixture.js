@@ -0,0 +1,4 @@
+'use strict';
+const assert = require('node:assert');
+
+assert(user.name === 'alice');
@@ -0,0 +1,31 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert, null, "assert(...args)");
+const _pasrt2 = _power_(assert.equal, assert, "assert.equal(x, ...rest)");
+const _pasrt3 = _power_(assert, null, "assert(truthy === falsy)", {
+    binexp: "==="
+});
+const _pasrt4 = _power_(assert.deepEqual, assert, "assert.deepEqual(...[foo, bar])");
+const _pasrt5 = _power_(assert.ok, assert, "assert.ok(...iter(), 'message')");
+const _pasrt6 = _power_(assert, null, "assert(falsy)");
+const _parg1 = _pasrt1.spreadRecorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.spreadRecorder(1);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt4.spreadRecorder(0);
+const _parg6 = _pasrt5.spreadRecorder(0);
+const _parg7 = _pasrt5.recorder(1);
+const _parg8 = _pasrt6.recorder(0);
+_pasrt1.run(_parg1.rec(args, 10, 10, 14));
+_pasrt2.run(_parg2.rec(x, 13, 13, 14), _parg3.rec(rest, 19, 19, 23));
+_pasrt3.run(_parg4.rec(_parg4.tap(truthy, 7, 7, 13, {
+    hint: "left"
+}) === _parg4.tap(falsy, 18, 18, 23, {
+    hint: "right"
+}), 14, 7, 23));
+_pasrt4.run(_parg5.rec([
+    _parg5.tap(foo, 21, 21, 24),
+    _parg5.tap(bar, 26, 26, 29)
+], 20, 20, 30));
+_pasrt5.run(_parg6.rec(iter(), 13, 13, 19), _parg7.rec('message', 21, 21, 30));
+_pasrt6.run(_parg8.rec(falsy, 7, 7, 12));
@@ -0,0 +1,13 @@
+import assert from 'node:assert';
+
+assert(...args);
+
+assert.equal(x, ...rest);
+
+assert(truthy === falsy);
+
+assert.deepEqual(...[foo, bar]);
+
+assert.ok(...iter(), 'message');
+
+assert(falsy);
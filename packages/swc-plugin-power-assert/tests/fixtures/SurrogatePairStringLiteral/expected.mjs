@@ -0,0 +1,38 @@
+import assert from 'node:assert/strict';
+import { _power_ } from "@power-assert/runtime";
+{
+    const _pasrt1 = _power_(assert, null, "assert(sushi !== '🍣')", {
+        binexp: "!=="
+    });
+    const _parg1 = _pasrt1.recorder(0);
+    _pasrt1.run(_parg1.rec(_parg1.tap(sushi, 7, 7, 12, {
+        hint: "left"
+    }) !== _parg1.tap('🍣', 17, 17, 21, {
+        hint: "right"
+    }), 13, 7, 21));
+}{
+    const _pasrt2 = _power_(assert, null, "assert('𠮷野家' !== '🍣🍺')", {
+        binexp: "!=="
+    });
+    const _parg2 = _pasrt2.recorder(0);
+    _pasrt2.run(_parg2.rec(_parg2.tap('𠮷野家', 7, 7, 13, {
+        hint: "left"
+    }) !== _parg2.tap('🍣🍺', 18, 18, 24, {
+        hint: "right"
+    }), 14, 7, 24));
+}{
+    const _pasrt3 = _power_(assert.equal, assert, "assert.equal(menu['🍣'].price, 100, '🍣は100円')");
+    const _parg3 = _pasrt3.recorder(0);
+    const _parg4 = _pasrt3.recorder(1);
+    _pasrt3.run(_parg3.rec(_parg3.tap(_parg3.tap(menu, 13, 13, 17)[_parg3.tap('🍣', 18, 18, 22)], 17, 13, 23).price, 24, 13, 29), _parg4.rec(100, 31, 31, 34), '🍣は100円');
+}{
+    const _pasrt4 = _power_(assert, null, "assert(names.includes('👨‍👩‍👧') === found)", {
+        binexp: "==="
+    });
+    const _parg5 = _pasrt4.recorder(0);
+    _pasrt4.run(_parg5.rec(_parg5.tap(_parg5.tap(names, 7, 7, 12).includes(_parg5.tap('👨‍👩‍👧', 22, 22, 32)), 13, 7, 33, {
+        hint: "left"
+    }) === _parg5.tap(found, 38, 38, 43, {
+        hint: "right"
+    }), 34, 7, 43));
+}
@@ -0,0 +1,14 @@
+import assert from 'node:assert/strict';
+
+{
+  assert(sushi !== '🍣');
+}
+{
+  assert('𠮷野家' !== '🍣🍺');
+}
+{
+  assert.equal(menu['🍣'].price, 100, '🍣は100円');
+}
+{
+  assert(names.includes('👨‍👩‍👧') === found);
+}
@@ -0,0 +1,17 @@
+import assert from 'node:assert/strict';
+import { _power_ } from "@power-assert/runtime";
+{
+    assert(foo === synthesized);
+}{
+    assert.equal(bar.baz, synthesized.qux);
+}{
+    const _pasrt1 = _power_(assert, null, "assert(foo === bar)", {
+        binexp: "==="
+    });
+    const _parg1 = _pasrt1.recorder(0);
+    _pasrt1.run(_parg1.rec(_parg1.tap(foo, 7, 7, 10, {
+        hint: "left"
+    }) === _parg1.tap(bar, 15, 15, 18, {
+        hint: "right"
+    }), 11, 7, 18));
+}
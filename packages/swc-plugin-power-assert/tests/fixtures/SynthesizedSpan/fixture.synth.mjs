@@ -0,0 +1,11 @@
+import assert from 'node:assert/strict';
+
+{
+  assert(foo === synthesized);
+}
+{
+  assert.equal(bar.baz, synthesized.qux);
+}
+{
+  assert(foo === bar);
+}
@@ -0,0 +1,6 @@
+import assert from 'node:assert';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert.deepEqual, assert, "assert.deepEqual(render(<Greeting<User> user={user as User} />), <p>Hello {user!.name}</p>)");
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt1.recorder(1);
+_pasrt1.run(_parg1.rec(render(_parg1.tap(<Greeting<User> user={_parg1.tap(_parg1.tap(user, 46, 46, 50) as User, 46, 46, 58)}/>, 24, 24, 62)), 17, 17, 63), _parg2.rec(<p>Hello {_parg2.tap(_parg2.tap(_parg2.tap(user, 75, 75, 79)!, 75, 75, 80).name, 81, 75, 85)}</p>, 65, 65, 90));
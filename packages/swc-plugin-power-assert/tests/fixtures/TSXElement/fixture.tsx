@@ -0,0 +1,3 @@
+import assert from 'node:assert';
+
+assert.deepEqual(render(<Greeting<User> user={user as User} />), <p>Hello {user!.name}</p>);
@@ -0,0 +1,38 @@
+import assert from 'node:assert/strict';
+import { _power_ } from "@power-assert/runtime";
+{
+    const _pasrt1 = _power_(assert, null, "assert(a /* ? */ ? b : c)");
+    const _parg1 = _pasrt1.recorder(0);
+    _pasrt1.run(_parg1.rec(_parg1.tap(a /* ? */ , 7, 7, 8) ? _parg1.tap(b, 19, 19, 20) : _parg1.tap(c, 23, 23, 24), 17, 7, 24));
+}{
+    const _pasrt2 = _power_(assert, null, "assert(x /*=*/ = y)");
+    const _parg2 = _pasrt2.recorder(0);
+    _pasrt2.run(_parg2.rec(x = _parg2.tap(y, 17, 17, 18), 15, 7, 18));
+}{
+    const _pasrt3 = _power_(assert, null, "assert((fn || '(') /* ( */ ('('))");
+    const _parg3 = _pasrt3.recorder(0);
+    _pasrt3.run(_parg3.rec(_parg3.tap(_parg3.tap(fn, 8, 8, 10) || _parg3.tap('(', 14, 14, 17), 11, 8, 17)(_parg3.tap('(', 28, 28, 31)), 27, 7, 32));
+}{
+    const _pasrt4 = _power_(assert, null, "assert(str /* === */ === '===')", {
+        binexp: "==="
+    });
+    const _parg4 = _pasrt4.recorder(0);
+    _pasrt4.run(_parg4.rec(_parg4.tap(str /* === */ , 7, 7, 10, {
+        hint: "left"
+    }) === _parg4.tap('===', 25, 25, 30, {
+        hint: "right"
+    }), 21, 7, 30));
+}{
+    const _pasrt5 = _power_(assert, null, "assert(left // &&\n    && right)");
+    const _parg5 = _pasrt5.recorder(0);
+    _pasrt5.run(_parg5.rec(_parg5.tap(left // &&
+    , 7, 7, 11) && _parg5.tap(right, 25, 25, 30), 22, 7, 30));
+}{
+    const _pasrt6 = _power_(assert, null, "assert(count /* ++ */ ++)");
+    const _parg6 = _pasrt6.recorder(0);
+    _pasrt6.run(_parg6.rec(count /* ++ */ ++, 22, 7, 24));
+}{
+    const _pasrt7 = _power_(assert, null, "assert(`${a}?` ? b : c)");
+    const _parg7 = _pasrt7.recorder(0);
+    _pasrt7.run(_parg7.rec(_parg7.tap(`${_parg7.tap(a, 10, 10, 11)}?`, 7, 7, 14) ? _parg7.tap(b, 17, 17, 18) : _parg7.tap(c, 21, 21, 22), 15, 7, 22));
+}
@@ -0,0 +1,24 @@
+import assert from 'node:assert/strict';
+
+{
+  assert(a /* ? */ ? b : c);
+}
+{
+  assert(x /*=*/ = y);
+}
+{
+  assert((fn || '(') /* ( */ ('('));
+}
+{
+  assert(str /* === */ === '===');
+}
+{
+  assert(left // &&
+    && right);
+}
+{
+  assert(count /* ++ */ ++);
+}
+{
+  assert(`${a}?` ? b : c);
+}
@@ -0,0 +1,31 @@
+import assert from 'node:assert/strict';
+import { _power_ } from "@power-assert/runtime";
+const _pasrt1 = _power_(assert, null, "assert((items[0] as Item).name === 'foo')", {
+    binexp: "==="
+});
+const _pasrt2 = _power_(assert.equal, assert, "assert.equal(items.length satisfies number, 1)");
+const _pasrt3 = _power_(assert, null, "assert(items.at(0)!.name)");
+const _pasrt4 = _power_(assert, null, "assert(<number>items.length === 0)", {
+    binexp: "==="
+});
+const _parg1 = _pasrt1.recorder(0);
+const _parg2 = _pasrt2.recorder(0);
+const _parg3 = _pasrt2.recorder(1);
+const _parg4 = _pasrt3.recorder(0);
+const _parg5 = _pasrt4.recorder(0);
+type Item = {
+    name: string;
+};
+const items: Item[] = [];
+_pasrt1.run(_parg1.rec(_parg1.tap(_parg1.tap(_parg1.tap(_parg1.tap(items, 8, 8, 13)[_parg1.tap(0, 14, 14, 15)], 13, 8, 16) as Item, 8, 8, 24).name, 26, 7, 30, {
+    hint: "left"
+}) === _parg1.tap('foo', 35, 35, 40, {
+    hint: "right"
+}), 31, 7, 40));
+_pasrt2.run(_parg2.rec(_parg2.tap(_parg2.tap(items, 13, 13, 18).length, 19, 13, 25) satisfies number, 13, 13, 42), _parg3.rec(1, 44, 44, 45));
+_pasrt3.run(_parg4.rec(_parg4.tap(_parg4.tap(_parg4.tap(items, 7, 7, 12).at(_parg4.tap(0, 16, 16, 17)), 13, 7, 18)!, 7, 7, 19).name, 20, 7, 24));
+_pasrt4.run(_parg5.rec(_parg5.tap(<number>_parg5.tap(_parg5.tap(items, 15, 15, 20).length, 21, 15, 27), 7, 7, 27, {
+    hint: "left"
+}) === _parg5.tap(0, 32, 32, 33, {
+    hint: "right"
+}), 28, 7, 33));
@@ -0,0 +1,9 @@
+import assert from 'node:assert/strict';
+
+type Item = { name: string };
+const items: Item[] = [];
+
+assert((items[0] as Item).name === 'foo');
+assert.equal(items.length satisfies number, 1);
+assert(items.at(0)!.name);
+assert(<number>items.length === 0);
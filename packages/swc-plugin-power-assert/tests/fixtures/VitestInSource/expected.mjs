@@ -0,0 +1,31 @@
+import { _power_ } from "@power-assert/runtime";
+export function add(...args) {
+    return args.reduce((a, b)=>a + b, 0);
+}
+if (import.meta.vitest) {
+    const { it, assert, expect: check } = import.meta.vitest;
+    it('add', ()=>{
+        const _pasrt1 = _power_(assert, null, "assert(add(1, 2) === 3)", {
+            binexp: "==="
+        });
+        const _pasrt2 = _power_(check, null, "check(add(1, 2, 3)).toBe(6)");
+        const _parg1 = _pasrt1.recorder(0);
+        const _parg2 = _pasrt2.recorder(0);
+        const _parg3 = _pasrt2.recorder(1);
+        _pasrt1.run(_parg1.rec(_parg1.tap(add(_parg1.tap(1, 11, 11, 12), _parg1.tap(2, 14, 14, 15)), 7, 7, 16, {
+            hint: "left"
+        }) === _parg1.tap(3, 21, 21, 22, {
+            hint: "right"
+        }), 17, 7, 22));
+        _pasrt2.runChain((_paexpect, _paval0, _paval1)=>_paexpect(_paval0).toBe(_paval1), _parg2.rec(add(_parg2.tap(1, 10, 10, 11), _parg2.tap(2, 13, 13, 14), _parg2.tap(3, 16, 16, 17)), 6, 6, 18), _parg3.rec(6, 25, 25, 26));
+    });
+}
+if (import.meta.vitest) {
+    const vt = import.meta.vitest;
+    vt.it('add', ()=>{
+        const _pasrt3 = _power_(vt.assert.equal, vt.assert, "vt.assert.equal(add(), 0)");
+        const _parg4 = _pasrt3.recorder(0);
+        const _parg5 = _pasrt3.recorder(1);
+        _pasrt3.run(_parg4.rec(add(), 16, 16, 21), _parg5.rec(0, 23, 23, 24));
+    });
+}
@@ -0,0 +1,18 @@
+export function add(...args) {
+  return args.reduce((a, b) => a + b, 0);
+}
+
+if (import.meta.vitest) {
+  const { it, assert, expect: check } = import.meta.vitest;
+  it('add', () => {
+    assert(add(1, 2) === 3);
+    check(add(1, 2, 3)).toBe(6);
+  });
+}
+
+if (import.meta.vitest) {
+  const vt = import.meta.vitest;
+  vt.it('add', () => {
+    vt.assert.equal(add(), 0);
+  });
+}
@@ -0,0 +1,264 @@
+//! Property tests of the positions passed to the runtime.
+//!
+//! Random assertions mixing multibyte identifiers, comments, newlines and
+//! nesting are instrumented, then for every `tap` / `rec` the
+//! `startPos..endPos` slice (in UTF-16 code units) of the assertion code has to
+//! re-parse to the captured expression, with `markerPos` lying inside it, and
+//! lie inside the range of its parent capture in the expression tree.
+
+use proptest::prelude::*;
+use proptest::test_runner::TestRunner;
+use rustc_hash::FxHashMap;
+use swc_core::common::{sync::Lrc, EqIgnoreSpan, FileName, SourceMap};
+use swc_core::ecma::ast::{
+    CallExpr,
+    Callee,
+    EsVersion,
+    Expr,
+    Lit,
+    MemberProp,
+    Prop,
+    PropName,
+    PropOrSpread,
+    Pass,
+    Pat,
+    Program,
+    VarDeclarator
+};
+use swc_core::ecma::atoms::Atom;
+use swc_core::ecma::visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+use swc_ecma_parser::{parse_file_as_expr, parse_file_as_module, EsSyntax, Syntax};
+use swc_plugin_power_assert::{power_assert, Config};
+
+#[derive(Debug)]
+struct CapturePoint {
+    assertion_code: String,
+    value: Expr,
+    marker_pos: usize,
+    start_pos: usize,
+    end_pos: usize,
+    // id and parent of the expression tree
+    id: usize,
+    parent: Option<usize>
+}
+
+#[derive(Default)]
+struct CapturePointCollector {
+    // `_pasrt1` -> assertion code
+    runners: FxHashMap<Atom, String>,
+    // `_parg1` -> assertion code
+    recorders: FxHashMap<Atom, String>,
+    capture_points: Vec<CapturePoint>
+}
+
+fn member_call(n: &CallExpr) -> Option<(&Atom, &Atom)> {
+    let Callee::Expr(callee) = &n.callee else {
+        return None;
+    };
+    let Expr::Member(member) = callee.as_ref() else {
+        return None;
+    };
+    match (member.obj.as_ref(), &member.prop) {
+        (Expr::Ident(obj), MemberProp::Ident(prop)) => Some((&obj.sym, &prop.sym)),
+        _ => None
+    }
+}
+
+fn expression_tree_metadata(n: &CallExpr) -> (Option<usize>, Option<usize>) {
+    let Some(Expr::Object(metadata)) = n.args.last().map(|arg| arg.expr.as_ref()) else {
+        return (None, None);
+    };
+    let number_of = |key: &str| metadata.props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match prop.as_ref() {
+            Prop::KeyValue(kv) if matches!(&kv.key, PropName::Ident(ident) if ident.sym == key) => match kv.value.as_ref() {
+                Expr::Lit(Lit::Num(num)) => Some(num.value as usize),
+                _ => None
+            },
+            _ => None
+        },
+        _ => None
+    });
+    (number_of("id"), number_of("parent"))
+}
+
+impl Visit for CapturePointCollector {
+    // declarations are hoisted before their usages, so they are visited first
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        let (Pat::Ident(binding), Some(Expr::Call(call))) = (&n.name, n.init.as_deref()) else {
+            return;
+        };
+        if let Callee::Expr(callee) = &call.callee {
+            if matches!(callee.as_ref(), Expr::Ident(ident) if ident.sym == "_power_") {
+                let Some(Expr::Lit(Lit::Str(code))) = call.args.get(2).map(|arg| arg.expr.as_ref()) else {
+                    panic!("assertion code not found");
+                };
+                self.runners.insert(binding.id.sym.clone(), code.value.to_string_lossy().into_owned());
+                return;
+            }
+        }
+        if let Some((obj, prop)) = member_call(call) {
+            if prop == "recorder" {
+                let assertion_code = self.runners[obj].clone();
+                self.recorders.insert(binding.id.sym.clone(), assertion_code);
+            }
+        }
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        n.visit_children_with(self);
+        let Some((obj, prop)) = member_call(n) else {
+            return;
+        };
+        if prop != "tap" && prop != "rec" {
+            return;
+        }
+        let positions: Vec<usize> = n.args.iter().skip(1).take(3).filter_map(|arg| match arg.expr.as_ref() {
+            Expr::Lit(Lit::Num(num)) => Some(num.value as usize),
+            _ => None
+        }).collect();
+        let [marker_pos, start_pos, end_pos] = positions[..] else {
+            // `rec` without positions
+            return;
+        };
+        let (id, parent) = expression_tree_metadata(n);
+        self.capture_points.push(CapturePoint {
+            assertion_code: self.recorders[obj].clone(),
+            value: *n.args[0].expr.clone(),
+            marker_pos,
+            start_pos,
+            end_pos,
+            id: id.expect("id of the expression tree not found"),
+            parent
+        });
+    }
+}
+
+// restores the original expression from the instrumented one
+struct Uninstrument;
+
+impl VisitMut for Uninstrument {
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        n.visit_mut_children_with(self);
+        if let Expr::Paren(paren) = n {
+            *n = *paren.expr.clone();
+            return;
+        }
+        if let Expr::Call(call) = n {
+            if member_call(call).is_some_and(|(obj, prop)| obj.starts_with("_parg") && (prop == "tap" || prop == "rec")) {
+                *n = *call.args[0].expr.clone();
+            }
+        }
+    }
+}
+
+fn parse_expr(cm: &Lrc<SourceMap>, code: &str) -> Option<Expr> {
+    // parenthesized, since `in` is not parsed at the top level of an expression
+    let fm = cm.new_source_file(FileName::Anon.into(), format!("(\n{}\n)", code));
+    let mut expr = parse_file_as_expr(&fm, Syntax::Es(EsSyntax::default()), EsVersion::latest(), None, &mut vec![]).ok()?;
+    expr.visit_mut_with(&mut Uninstrument);
+    Some(*expr)
+}
+
+fn utf16_slice(code: &str, start: usize, end: usize) -> Option<String> {
+    let utf16: Vec<u16> = code.encode_utf16().collect();
+    String::from_utf16(utf16.get(start..end)?).ok()
+}
+
+fn check_positions(assertion: &str) -> Result<(), TestCaseError> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), format!("import assert from 'node:assert';\n{}\n", assertion));
+    let module = parse_file_as_module(&fm, Syntax::Es(EsSyntax::default()), EsVersion::latest(), None, &mut vec![]);
+    prop_assume!(module.is_ok(), "generated code does not parse");
+    let mut program = Program::Module(module.unwrap());
+    power_assert(Config::builder().expression_tree(true).build(), cm.clone()).process(&mut program);
+
+    let mut collector = CapturePointCollector::default();
+    program.visit_with(&mut collector);
+    prop_assert_eq!(collector.runners.len(), 1, "assertion is not instrumented");
+    prop_assert!(!collector.capture_points.is_empty());
+    let ranges: FxHashMap<usize, (usize, usize)> = collector.capture_points.iter().map(|capture_point| (capture_point.id, (capture_point.start_pos, capture_point.end_pos))).collect();
+    prop_assert_eq!(ranges.len(), collector.capture_points.len(), "ids are not unique");
+    for capture_point in collector.capture_points {
+        let CapturePoint { assertion_code, mut value, marker_pos, start_pos, end_pos, id, parent } = capture_point;
+        if let Some(parent) = parent {
+            prop_assert!(parent < id, "parent {} of {} is not visited before", parent, id);
+            let (parent_start_pos, parent_end_pos) = ranges[&parent];
+            prop_assert!(parent_start_pos <= start_pos && end_pos <= parent_end_pos, "{}..{} is not in {}..{} of the parent in {:?}", start_pos, end_pos, parent_start_pos, parent_end_pos, assertion_code);
+        }
+        prop_assert!(start_pos <= marker_pos && marker_pos < end_pos, "marker {} is not in {}..{} of {:?}", marker_pos, start_pos, end_pos, assertion_code);
+        let slice = utf16_slice(&assertion_code, start_pos, end_pos);
+        prop_assert!(slice.is_some(), "{}..{} is out of {:?}", start_pos, end_pos, assertion_code);
+        let slice = slice.unwrap();
+        let reparsed = parse_expr(&cm, &slice);
+        prop_assert!(reparsed.is_some(), "{:?} ({}..{} of {:?}) does not parse", slice, start_pos, end_pos, assertion_code);
+        value.visit_mut_with(&mut Uninstrument);
+        prop_assert!(reparsed.unwrap().eq_ignore_span(&value), "{:?} ({}..{} of {:?}) is not the captured expression", slice, start_pos, end_pos, assertion_code);
+    }
+    Ok(())
+}
+
+fn gap() -> impl Strategy<Value = &'static str> {
+    prop_oneof![
+        4 => Just(""),
+        2 => Just(" "),
+        1 => Just("\n  "),
+        1 => Just("/* コメント */"),
+        1 => Just(" /*🍣*/ "),
+        1 => Just("// 𠮷\n"),
+    ]
+}
+
+fn ident() -> impl Strategy<Value = &'static str> {
+    prop_oneof![Just("foo"), Just("ほげ"), Just("𠮷野家"), Just("$ñ"), Just("_x1"), Just("ｆｕｇａ")]
+}
+
+fn literal() -> impl Strategy<Value = &'static str> {
+    prop_oneof![Just("1.5"), Just("'ほげ'"), Just("\"🍣 👨‍👩‍👧\""), Just("null"), Just("true"), Just("`𠮷`"), Just("/ほ+げ/u")]
+}
+
+fn binary_op() -> impl Strategy<Value = &'static str> {
+    prop_oneof![Just("==="), Just("!=="), Just("+"), Just("*"), Just("&&"), Just("||"), Just("??"), Just("in"), Just("instanceof"), Just("<")]
+}
+
+fn unary_op() -> impl Strategy<Value = &'static str> {
+    prop_oneof![Just("!"), Just("-"), Just("typeof "), Just("void "), Just("~")]
+}
+
+// every generated expression can be used as an operand without extra parentheses
+fn expression() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![ident().prop_map(String::from), literal().prop_map(String::from)];
+    leaf.prop_recursive(4, 24, 3, |inner| {
+        prop_oneof![
+            (inner.clone(), gap(), gap(), ident()).prop_map(|(obj, g1, g2, prop)| format!("{}{}.{}{}", obj, g1, g2, prop)),
+            (inner.clone(), gap(), inner.clone(), gap()).prop_map(|(obj, g1, prop, g2)| format!("{}[{}{}{}]", obj, g1, prop, g2)),
+            (inner.clone(), gap(), prop::collection::vec(inner.clone(), 0..3)).prop_map(|(callee, g, args)| format!("{}{}({})", callee, g, args.join(", "))),
+            (ident(), gap(), prop::collection::vec(inner.clone(), 0..2)).prop_map(|(callee, g, args)| format!("(new {}{}({}))", callee, g, args.join(","))),
+            (inner.clone(), gap(), binary_op(), gap(), inner.clone()).prop_map(|(left, g1, op, g2, right)| format!("({}{} {} {}{})", left, g1, op, g2, right)),
+            (unary_op(), gap(), inner.clone()).prop_map(|(op, g, arg)| format!("({}{}{})", op, g, arg)),
+            (inner.clone(), gap(), inner.clone(), gap(), inner.clone()).prop_map(|(test, g1, cons, g2, alt)| format!("({} ?{}{} :{}{})", test, g1, cons, g2, alt)),
+            prop::collection::vec((gap(), inner.clone()), 0..3).prop_map(|elems| format!("[{}]", elems.into_iter().map(|(g, e)| format!("{}{}", g, e)).collect::<Vec<_>>().join(","))),
+            (ident(), inner.clone(), gap(), inner.clone()).prop_map(|(key, value, g, computed)| format!("({{ {}: {},{}[{}]: 1 }})", key, value, g, computed)),
+            (inner.clone(), gap(), inner.clone()).prop_map(|(first, g, second)| format!("`ほ${{{}}}{}げ${{{}}}`", first, g, second)),
+        ]
+    })
+}
+
+fn assertion() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (gap(), expression(), gap()).prop_map(|(g1, expr, g2)| format!("assert({}{}{});", g1, expr, g2)),
+        (expression(), gap(), expression()).prop_map(|(actual, g, expected)| format!("assert.deepEqual({},{}{});", actual, g, expected)),
+        (gap(), expression()).prop_map(|(g, expr)| format!("ほげ({{ a: assert.ok({}{}) }});", g, expr)),
+    ]
+}
+
+#[test]
+fn positions_point_to_captured_expressions() {
+    // generating and parsing nested expressions needs more stack than test threads have in debug builds
+    let runner = std::thread::Builder::new().stack_size(32 * 1024 * 1024).spawn(|| {
+        let mut runner = TestRunner::new(ProptestConfig::with_cases(512));
+        if let Err(err) = runner.run(&assertion(), |assertion| check_positions(&assertion)) {
+            panic!("{}", err);
+        }
+    }).unwrap();
+    runner.join().unwrap();
+}
@@ -0,0 +1,21 @@
+import assert from 'node:assert/strict';
+import {_power_} from "@power-assert/runtime";
+{
+  const _pasrt1 = _power_(assert, null, "assert(false, 'message')");
+  const _parg1 = _pasrt1.recorder(0);
+  const _parg2 = _pasrt1.recorder(1);
+  _pasrt1.run(_parg1.rec(false, 7, 7, 12), _parg2.rec('message', 14, 14, 23));
+}
+{
+  const _pasrt2 = _power_(assert, null, "assert(false, messageStr)");
+  const _parg3 = _pasrt2.recorder(0);
+  const _parg4 = _pasrt2.recorder(1);
+  _pasrt2.run(_parg3.rec(false, 7, 7, 12), _parg4.rec(messageStr, 14, 14, 24));
+}
+{
+  const _pasrt3 = _power_(assert.equal, assert, "assert.equal(foo, 'bar', 'msg')");
+  const _parg5 = _pasrt3.recorder(0);
+  const _parg6 = _pasrt3.recorder(1);
+  const _parg7 = _pasrt3.recorder(2);
+  _pasrt3.run(_parg5.rec(foo, 13, 13, 16), _parg6.rec('bar', 18, 18, 23), _parg7.rec('msg', 25, 25, 30));
+}
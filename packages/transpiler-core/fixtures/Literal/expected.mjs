@@ -17,36 +17,17 @@ import {_power_} from "@power-assert/runtime";
   _pasrt3.run(_parg3.rec(1, 13, 13, 14), _parg4.rec(0, 16, 16, 17));
 }
 {
-  const _pasrt4 = _power_(assert, null, "assert(false, 'message')");
+  const _pasrt4 = _power_(assert, null, "assert(/^not/.exec(str))");
   const _parg5 = _pasrt4.recorder(0);
-  const _parg6 = _pasrt4.recorder(1);
-  _pasrt4.run(_parg5.rec(false, 7, 7, 12), _parg6.rec('message', 14, 14, 23));
+  _pasrt4.run(_parg5.rec(_parg5.tap(/^not/, 7, 7, 13).exec(_parg5.tap(str, 19, 19, 22)), 14, 7, 23));
 }
 {
-  const _pasrt5 = _power_(assert, null, "assert(false, messageStr)");
-  const _parg7 = _pasrt5.recorder(0);
-  const _parg8 = _pasrt5.recorder(1);
-  _pasrt5.run(_parg7.rec(false, 7, 7, 12), _parg8.rec(messageStr, 14, 14, 24));
+  const _pasrt5 = _power_(assert, null, "assert(0b111110111)");
+  const _parg6 = _pasrt5.recorder(0);
+  _pasrt5.run(_parg6.rec(0b111110111, 7, 7, 18));
 }
 {
-  const _pasrt6 = _power_(assert.equal, assert, "assert.equal(foo, 'bar', 'msg')");
-  const _parg9 = _pasrt6.recorder(0);
-  const _parg10 = _pasrt6.recorder(1);
-  const _parg11 = _pasrt6.recorder(2);
-  _pasrt6.run(_parg9.rec(foo, 13, 13, 16), _parg10.rec('bar', 18, 18, 23), _parg11.rec('msg', 25, 25, 30));
-}
-{
-  const _pasrt7 = _power_(assert, null, "assert(/^not/.exec(str))");
-  const _parg12 = _pasrt7.recorder(0);
-  _pasrt7.run(_parg12.rec(_parg12.tap(/^not/, 7, 7, 13).exec(_parg12.tap(str, 19, 19, 22)), 14, 7, 23));
-}
-{
-  const _pasrt8 = _power_(assert, null, "assert(0b111110111)");
-  const _parg13 = _pasrt8.recorder(0);
-  _pasrt8.run(_parg13.rec(0b111110111, 7, 7, 18));
-}
-{
-  const _pasrt9 = _power_(assert, null, "assert(0o767)");
-  const _parg14 = _pasrt9.recorder(0);
-  _pasrt9.run(_parg14.rec(0o767, 7, 7, 12));
+  const _pasrt6 = _power_(assert, null, "assert(0o767)");
+  const _parg7 = _pasrt6.recorder(0);
+  _pasrt6.run(_parg7.rec(0o767, 7, 7, 12));
 }
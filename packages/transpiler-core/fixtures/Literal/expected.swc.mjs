@@ -0,0 +1,18 @@
+import assert from 'node:assert/strict';
+import {_power_} from "@power-assert/runtime";
+{
+  const _pasrt1 = _power_(assert, null, "assert(false, 'message')");
+  const _parg1 = _pasrt1.recorder(0);
+  _pasrt1.run(_parg1.rec(false, 7, 7, 12), 'message');
+}
+{
+  const _pasrt2 = _power_(assert, null, "assert(false, messageStr)");
+  const _parg2 = _pasrt2.recorder(0);
+  _pasrt2.run(_parg2.rec(false, 7, 7, 12), messageStr);
+}
+{
+  const _pasrt3 = _power_(assert.equal, assert, "assert.equal(foo, 'bar', 'msg')");
+  const _parg3 = _pasrt3.recorder(0);
+  const _parg4 = _pasrt3.recorder(1);
+  _pasrt3.run(_parg3.rec(foo, 13, 13, 16), _parg4.rec('bar', 18, 18, 23), 'msg');
+}
@@ -0,0 +1,10 @@
+import assert from 'node:assert/strict';
+{
+  assert(false, 'message');
+}
+{
+  assert(false, messageStr);
+}
+{
+  assert.equal(foo, 'bar', 'msg');
+}
@@ -9,15 +9,6 @@ import assert from 'node:assert/strict';
 {
   assert.equal(1, 0);
 }
-{
-  assert(false, 'message');
-}
-{
-  assert(false, messageStr);
-}
-{
-  assert.equal(foo, 'bar', 'msg');
-}
 {
   assert(/^not/.exec(str));
 }
@@ -0,0 +1,18 @@
+import assert from 'node:assert/strict';
+import {_power_} from "@power-assert/runtime";
+{
+  const _pasrt1 = _power_(assert, null, "assert({[num]: foo}, 'Computed (dynamic) property names')");
+  const _parg1 = _pasrt1.recorder(0);
+  const _parg2 = _pasrt1.recorder(1);
+  _pasrt1.run(_parg1.rec({
+    [_parg1.tap(num, 9, 9, 12)]: _parg1.tap(foo, 15, 15, 18)
+  }, 7, 7, 19), _parg2.rec('Computed (dynamic) property names', 21, 21, 56));
+}
+{
+  const _pasrt2 = _power_(assert, null, "assert({foo}, 'shorthand literal itself will not be instrumented')");
+  const _parg3 = _pasrt2.recorder(0);
+  const _parg4 = _pasrt2.recorder(1);
+  _pasrt2.run(_parg3.rec({
+    foo
+  }, 7, 7, 12), _parg4.rec('shorthand literal itself will not be instrumented', 14, 14, 65));
+}
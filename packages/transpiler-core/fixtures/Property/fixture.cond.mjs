@@ -0,0 +1,7 @@
+import assert from 'node:assert/strict';
+{
+  assert({[num]: foo}, 'Computed (dynamic) property names');
+}
+{
+  assert({foo}, 'shorthand literal itself will not be instrumented');
+}
@@ -0,0 +1,7 @@
+import assert from 'node:assert/strict';
+{
+  assert(...iter());
+}
+{
+  assert(...[foo, bar]);
+}
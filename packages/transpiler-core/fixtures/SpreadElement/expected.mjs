@@ -15,9 +15,3 @@ import {_power_} from "@power-assert/runtime";
   const _parg3 = _pasrt3.recorder(0);
   _pasrt3.run(_parg3.rec(f(_parg3.tap(head, 9, 9, 13), ..._parg3.tap(iter(), 18, 18, 24), ..._parg3.tap([_parg3.tap(foo, 30, 30, 33), _parg3.tap(bar, 35, 35, 38)], 29, 29, 39)), 7, 7, 40));
 }
-{
-  assert(...iter());
-}
-{
-  assert(...[foo, bar]);
-}
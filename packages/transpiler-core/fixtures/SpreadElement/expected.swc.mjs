@@ -0,0 +1,12 @@
+import assert from 'node:assert/strict';
+import {_power_} from "@power-assert/runtime";
+{
+  const _pasrt1 = _power_(assert, null, "assert(...iter())");
+  const _parg1 = _pasrt1.spreadRecorder(0);
+  _pasrt1.run(_parg1.rec(iter(), 10, 10, 16));
+}
+{
+  const _pasrt2 = _power_(assert, null, "assert(...[foo, bar])");
+  const _parg2 = _pasrt2.spreadRecorder(0);
+  _pasrt2.run(_parg2.rec([_parg2.tap(foo, 11, 11, 14), _parg2.tap(bar, 16, 16, 19)], 10, 10, 20));
+}
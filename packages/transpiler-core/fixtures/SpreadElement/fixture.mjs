@@ -9,9 +9,3 @@ import assert from 'node:assert/strict';
 {
   assert(f(head, ...iter(), ...[foo, bar]));
 }
-{
-  assert(...iter());
-}
-{
-  assert(...[foo, bar]);
-}